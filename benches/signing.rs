@@ -0,0 +1,47 @@
+//! Benchmarks for request serialization and TC3 signing
+//!
+//! Usage:
+//! ```
+//! cargo bench --bench signing
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tencentcloud_sms_sdk::{Client, Credential, SendSmsRequest};
+
+fn request_with_recipients(count: usize) -> SendSmsRequest {
+    let phone_numbers = (0..count).map(|i| format!("+86138{:08}", i)).collect();
+    SendSmsRequest::new(
+        phone_numbers,
+        "1400000000",
+        "123456",
+        "YourSignature",
+        vec!["123456".to_string(), "5".to_string()],
+    )
+}
+
+fn bench_signing(c: &mut Criterion) {
+    let credential = Credential::new("bench_secret_id", "bench_secret_key", None);
+    let client = Client::new(credential, "ap-guangzhou");
+    let timestamp = 1_700_000_000;
+
+    let mut group = c.benchmark_group("sign_send_sms");
+    for recipients in [1, 200] {
+        let request = request_with_recipients(recipients);
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(recipients),
+            &request,
+            |b, request| {
+                b.iter(|| {
+                    client
+                        .sign_headers("SendSms", black_box(request), black_box(timestamp))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_signing);
+criterion_main!(benches);
@@ -17,9 +17,7 @@
 //! cargo run --example send_sms
 //! ```
 
-use tencentcloud_sms_sdk::{
-    Client, ClientProfile, Credential, HttpProfile, SendSmsRequest, TencentCloudError,
-};
+use tencentcloud_sms_sdk::{Client, ClientProfile, Credential, HttpProfile, SendSmsRequest};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
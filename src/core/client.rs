@@ -1,16 +1,57 @@
 //! Main client for TencentCloud API requests
 
-use crate::core::{ClientProfile, Credential};
+use crate::core::lifecycle::InFlight;
+use crate::core::profile::endpoint_host;
+use crate::core::retry::{splitmix64, time_seed};
+use crate::core::signature::{sha256_hex, Signer};
+use crate::core::telemetry;
+use crate::core::transport::Transport;
+use crate::core::{
+    ClientProfile, ConnectionPoolStats, Credential, RateLimitInfo, RawResponse, RequestId,
+};
 use crate::error::{Result, TencentCloudError};
-use crate::sms::{SendSmsRequest, SendSmsResponse};
-use chrono::Utc;
+use crate::sms::batch::BatchRun;
+use crate::sms::{
+    AddSmsSignRequest, AddSmsSignResponse, BatchEvent, BatchOptions, BatchSendResult, ChunkResult,
+    CostEstimate, DeliveryReport, DeliveryResult, DeliveryState, DescribeSignListStatus,
+    DescribeSmsSignListRequest, DescribeSmsSignListResponse, DescribeSmsTemplateListRequest,
+    DescribeSmsTemplateListResponse, PullSmsSendStatusByPhoneNumberRequest,
+    PullSmsSendStatusByPhoneNumberResponse, SendSmsRequest, SendSmsResponse, SignStatus,
+};
+use futures_util::stream::{self, Stream};
 use reqwest;
 use serde_json;
-use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
-use tencentcloud_sign_sdk::{sha256_hex, Tc3Signer};
+use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
+
+/// A signed request ready to be sent over HTTP
+#[derive(Debug, Clone)]
+struct SignedRequest {
+    /// HTTP method
+    method: String,
+    /// Full request URL
+    url: String,
+    /// Headers in the order they are sent
+    headers: Vec<(String, String)>,
+    /// Request body
+    body: String,
+}
 
 /// Main client for TencentCloud SMS API
+///
+/// Clones are cheap: they share the signer, the HTTP transport and the
+/// in-flight request tracking, so [`Client::shutdown`] on one clone drains
+/// them all. Setters on a clone only affect that clone.
+///
+/// The transport pools connections: sequential calls reuse an idle
+/// connection instead of opening a new one and repeating the TLS handshake,
+/// so long-lived senders should hold one client (or clones of it) for the
+/// process lifetime rather than building a client per send. Idle connections
+/// are closed after 90 seconds; [`Client::connection_pool_stats`] reports how
+/// many requests reused one.
+#[derive(Clone)]
 pub struct Client {
     /// Credentials for authentication
     credential: Credential,
@@ -18,12 +59,16 @@ pub struct Client {
     region: String,
     /// Client configuration profile
     profile: ClientProfile,
-    /// HTTP client
-    http_client: reqwest::Client,
+    /// HTTP transport, possibly shared with other clients
+    transport: Arc<Transport>,
     /// Service name (always "sms" for SMS service)
     service: String,
-    /// TC3 signer for request signing
-    signer: Tc3Signer,
+    /// TC3 signer for request signing, shared by clones
+    signer: Arc<Signer>,
+    /// Requests currently running, for graceful shutdown
+    in_flight: Arc<InFlight>,
+    /// Completed once the startup delay has passed
+    startup: Arc<OnceCell<()>>,
 }
 
 impl Client {
@@ -46,6 +91,41 @@ impl Client {
         Self::with_profile(credential, region, ClientProfile::new())
     }
 
+    /// Create a new client, rejecting a malformed region
+    ///
+    /// Regions look like `ap-guangzhou` or `na-siliconvalley`; a typo such as
+    /// `ap_guangzhou` would otherwise only surface as an API error.
+    pub fn try_new<S: Into<String>>(credential: Credential, region: S) -> Result<Self> {
+        let region = region.into();
+        validate_region(&region)?;
+        Ok(Self::new(credential, region))
+    }
+
+    /// Create a new client for the `ap-guangzhou` region
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::{Client, Credential};
+    ///
+    /// let credential = Credential::new("your_secret_id", "your_secret_key", None);
+    /// let client = Client::guangzhou(credential);
+    /// assert_eq!(client.region(), "ap-guangzhou");
+    /// ```
+    pub fn guangzhou(credential: Credential) -> Self {
+        Self::new(credential, "ap-guangzhou")
+    }
+
+    /// Create a new client for the `ap-beijing` region
+    pub fn beijing(credential: Credential) -> Self {
+        Self::new(credential, "ap-beijing")
+    }
+
+    /// Create a new client for the `ap-nanjing` region
+    pub fn nanjing(credential: Credential) -> Self {
+        Self::new(credential, "ap-nanjing")
+    }
+
     /// Create a new client with custom profile
     ///
     /// # Arguments
@@ -70,43 +150,60 @@ impl Client {
         region: S,
         profile: ClientProfile,
     ) -> Self {
-        let http_profile = profile.get_http_profile();
-
-        let mut client_builder = reqwest::Client::builder()
-            .timeout(http_profile.get_req_timeout())
-            .connect_timeout(http_profile.get_connect_timeout())
-            .tcp_keepalive(if http_profile.keep_alive {
-                Some(Duration::from_secs(60))
-            } else {
-                None
-            })
-            .user_agent(&http_profile.user_agent);
-
-        // Configure proxy if set
-        if let Some(proxy_url) = http_profile.get_proxy_url() {
-            if let Ok(proxy) = reqwest::Proxy::all(&proxy_url) {
-                client_builder = client_builder.proxy(proxy);
-            }
-        }
+        let transport = Arc::new(Transport::new(&profile));
+        Self::with_shared_transport(credential, region, profile, transport)
+    }
 
-        let http_client = client_builder
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new());
+    /// Create a new client that uses an existing HTTP client
+    ///
+    /// The timeouts, keep-alive and proxy settings of the profile's
+    /// [`HttpProfile`](crate::core::HttpProfile) only apply to HTTP clients built
+    /// by the SDK; a client passed here is used as configured by the caller.
+    /// See [`ClientPool`](crate::core::ClientPool) for sharing one transport
+    /// across several clients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::{Client, ClientProfile, Credential};
+    ///
+    /// let http_client = reqwest::Client::new();
+    /// let credential = Credential::new("your_secret_id", "your_secret_key", None);
+    /// let client = Client::with_http_client(
+    ///     credential,
+    ///     "ap-guangzhou",
+    ///     ClientProfile::new(),
+    ///     http_client,
+    /// );
+    /// ```
+    pub fn with_http_client<S: Into<String>>(
+        credential: Credential,
+        region: S,
+        profile: ClientProfile,
+        http_client: reqwest::Client,
+    ) -> Self {
+        let transport = Arc::new(Transport::external(http_client));
+        Self::with_shared_transport(credential, region, profile, transport)
+    }
 
-        let signer = Tc3Signer::new(
-            credential.secret_id().to_string(),
-            credential.secret_key().to_string(),
-            "sms".to_string(),
-            profile.is_debug(),
-        );
+    /// Create a new client around a shared HTTP transport
+    pub(crate) fn with_shared_transport<S: Into<String>>(
+        credential: Credential,
+        region: S,
+        profile: ClientProfile,
+        transport: Arc<Transport>,
+    ) -> Self {
+        let signer = build_signer(&credential, &profile);
 
         Self {
             credential,
             region: region.into(),
             profile,
-            http_client,
+            transport,
             service: "sms".to_string(),
             signer,
+            in_flight: Arc::new(InFlight::default()),
+            startup: Arc::new(OnceCell::new()),
         }
     }
 
@@ -139,8 +236,607 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
+    ///
+    /// # Cancellation
+    ///
+    /// The returned future is cancellation safe: it can be dropped at any await
+    /// point, for example by `tokio::select!` or `tokio::time::timeout`. The
+    /// call holds a [`ClientProfile::set_max_concurrency`] permit and an
+    /// in-flight entry for [`Client::shutdown`] across its awaits, retries
+    /// included, but both are guards released when the future is dropped.
+    /// Every attempt is signed from scratch, so the client stays usable after
+    /// a cancelled call. A request already written to the socket may still be
+    /// processed by the API, so a cancelled send can still deliver messages.
     pub async fn send_sms(&self, request: SendSmsRequest) -> Result<SendSmsResponse> {
-        self.make_request("SendSms", &request).await
+        self.check_category_policy(&request)?;
+        self.startup_delay().await;
+        if self.profile.is_explicit_nulls() {
+            self.make_request("SendSms", &request.to_json_with_nulls())
+                .await
+        } else {
+            self.make_request("SendSms", &request).await
+        }
+    }
+
+    /// Estimate the segments a send would be billed for, without sending
+    ///
+    /// Validates the request, then estimates the segments of the message
+    /// from `template_content` and the template parameters, see
+    /// [`SendSmsRequest::estimate_segments`]. Without the template text the
+    /// estimate only covers the signature and parameters.
+    pub fn estimate(
+        &self,
+        request: &SendSmsRequest,
+        template_content: Option<&str>,
+    ) -> Result<CostEstimate> {
+        request.validate().map_err(TencentCloudError::parameter)?;
+        let recipient_count = request.phone_number_set.len();
+        let segments = request.estimate_segments(template_content).segments;
+        Ok(CostEstimate {
+            recipient_count,
+            estimated_segments_per_recipient: segments,
+            estimated_total_segments: recipient_count * segments,
+        })
+    }
+
+    /// Send SMS through a template registered in the profile
+    ///
+    /// Looks up the signature of `template_id` in the profile's
+    /// [`TemplateRegistry`](crate::sms::TemplateRegistry) and checks that
+    /// `params` has the registered number of parameters before sending.
+    pub async fn send_for_template(
+        &self,
+        template_id: &str,
+        phone_number_set: Vec<String>,
+        params: Vec<String>,
+    ) -> Result<SendSmsResponse> {
+        let registry = self
+            .profile
+            .get_template_registry()
+            .ok_or_else(|| TencentCloudError::config("No template registry is configured"))?;
+        let request = registry.request(template_id, phone_number_set, params)?;
+        self.send_sms(request).await
+    }
+
+    /// Send SMS message, giving up at an absolute deadline
+    ///
+    /// Behaves like [`Client::send_sms`] bounded by the time left until
+    /// `deadline`, for callers that propagate a deadline rather than a
+    /// timeout. A deadline that has already passed fails with a timeout error
+    /// without sending anything.
+    pub async fn send_sms_by_deadline(
+        &self,
+        request: SendSmsRequest,
+        deadline: std::time::Instant,
+    ) -> Result<SendSmsResponse> {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            return Err(TencentCloudError::timeout("Deadline has already passed"));
+        }
+        tokio::time::timeout(remaining, self.send_sms(request))
+            .await
+            .unwrap_or_else(|_| {
+                Err(TencentCloudError::timeout(format!(
+                    "SendSms did not complete within the {:?} left before the deadline",
+                    remaining
+                )))
+            })
+    }
+
+    /// Send SMS to any number of recipients, split into chunks
+    ///
+    /// The phone numbers of `request` are sent in chunks of
+    /// `options.chunk_size`, one after another, with the other fields copied
+    /// to every chunk. A failed chunk does not stop the batch unless
+    /// `options.abort_on_failure_rate` is set and exceeded; see
+    /// [`BatchOptions::set_abort_on_failure_rate`].
+    ///
+    /// Each chunk is first counted against the profile's send quotas (see
+    /// [`ClientProfile::set_minute_quota`]), which may pause the batch or stop
+    /// it with the quota error in [`BatchSendResult::stopped_by`].
+    pub async fn send_sms_all(
+        &self,
+        request: SendSmsRequest,
+        options: &BatchOptions,
+    ) -> BatchSendResult {
+        self.send_sms_all_cancellable(request, options, &CancellationToken::new())
+            .await
+    }
+
+    /// Send SMS to any number of recipients in chunks, until cancelled
+    ///
+    /// Behaves like [`Client::send_sms_all`], but checks `cancel` before each
+    /// chunk and while waiting for send quota. Once it is cancelled no further
+    /// chunks are sent and the partial result is returned with
+    /// [`BatchSendResult::cancelled`] set and the remaining numbers in
+    /// [`BatchSendResult::unsent`]. A chunk already being sent is allowed to
+    /// complete, so its numbers are never left in doubt about whether they
+    /// were billed.
+    pub async fn send_sms_all_cancellable(
+        &self,
+        request: SendSmsRequest,
+        options: &BatchOptions,
+        cancel: &CancellationToken,
+    ) -> BatchSendResult {
+        let mut run = BatchRun::new(request, options);
+        let mut result = BatchSendResult::default();
+
+        while let Some(event) = self.next_batch_event(&mut run, cancel).await {
+            match event {
+                BatchEvent::Chunk(chunk) => result.chunks.push(chunk),
+                BatchEvent::Stopped(stop) => {
+                    result.unsent = stop.unsent;
+                    result.aborted = true;
+                    result.cancelled = stop.cancelled;
+                    result.stopped_by = stop.stopped_by;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Advance a chunked send by one chunk
+    ///
+    /// Checks `cancel`, counts the next chunk against the send quotas, sends
+    /// it and applies the failure-rate threshold. Returns `None` once the
+    /// batch is finished.
+    async fn next_batch_event(
+        &self,
+        run: &mut BatchRun,
+        cancel: &CancellationToken,
+    ) -> Option<BatchEvent> {
+        if let Some(stop) = run.take_pending_stop() {
+            return Some(stop);
+        }
+        let chunk = run.take_chunk()?;
+        if cancel.is_cancelled() {
+            return Some(run.stop(chunk, true, None));
+        }
+
+        // A quota wait can last up to a minute; cancelling ends it early
+        // and the chunk is left unsent
+        let acquired = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => None,
+            acquired = self.profile.quota.acquire(chunk.len() as u32) => Some(acquired),
+        };
+        match acquired {
+            None => return Some(run.stop(chunk, true, None)),
+            Some(Err(e)) => return Some(run.stop(chunk, false, Some(e))),
+            Some(Ok(())) => {}
+        }
+
+        let mut chunk_request = run.request.clone();
+        chunk_request.phone_number_set = chunk.clone();
+        let segments = chunk_request.estimate_segments(run.options.template_content.as_deref());
+        let result = self.send_sms(chunk_request).await;
+        Some(run.record(ChunkResult {
+            phone_numbers: chunk,
+            result,
+            segments,
+        }))
+    }
+
+    /// Send SMS, then re-send to numbers that failed transiently
+    ///
+    /// After the first send, numbers whose status is retryable (see
+    /// [`SendStatusCode::is_retryable`](crate::sms::SendStatusCode::is_retryable))
+    /// are sent again after the profile's retry backoff, for up to
+    /// `max_rounds` extra rounds. Invalid numbers and sign or template
+    /// failures are never re-sent. Each retried number's status is replaced
+    /// by its latest one; the request ID is that of the first send.
+    ///
+    /// An error on the first send is returned. An error on a later round stops
+    /// retrying and the statuses gathered so far are returned.
+    pub async fn send_with_retry_failed(
+        &self,
+        request: SendSmsRequest,
+        max_rounds: u32,
+    ) -> Result<SendSmsResponse> {
+        let mut response = self.send_sms(request.clone()).await?;
+        let backoff = self.profile.get_retry_profile().get_backoff();
+
+        for round in 1..=max_rounds {
+            let failed: Vec<String> = response
+                .send_status_set
+                .iter()
+                .filter(|status| status.status_code().is_retryable())
+                .map(|status| status.phone_number.clone())
+                .collect();
+            if failed.is_empty() {
+                break;
+            }
+
+            tokio::time::sleep(backoff.next_delay(round)).await;
+            let mut retry_request = request.clone();
+            retry_request.phone_number_set = failed;
+            let retried = match self.send_sms(retry_request).await {
+                Ok(retried) => retried,
+                Err(e) => {
+                    log::warn!("Stopping retry of failed numbers at round {}: {}", round, e);
+                    break;
+                }
+            };
+
+            for status in retried.send_status_set {
+                if let Some(slot) = response
+                    .send_status_set
+                    .iter_mut()
+                    .find(|slot| slot.phone_number == status.phone_number)
+                {
+                    *slot = status;
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send SMS to any number of recipients, yielding each chunk as it completes
+    ///
+    /// Chunks are sent one after another as the stream is polled, exactly as
+    /// [`Client::send_sms_all`] sends them, including the quota checks and the
+    /// failure-rate threshold, but each chunk is yielded as a
+    /// [`BatchEvent::Chunk`] as soon as its response arrives so progress can
+    /// be persisted incrementally. A batch that stops early, on the
+    /// failure-rate threshold or a quota error, ends with a
+    /// [`BatchEvent::Stopped`] listing the unsent numbers. Dropping the stream
+    /// stops the batch.
+    pub fn send_sms_stream(
+        &self,
+        request: SendSmsRequest,
+        options: &BatchOptions,
+    ) -> impl Stream<Item = BatchEvent> + '_ {
+        self.batch_stream(BatchRun::new(request, options), CancellationToken::new())
+    }
+
+    /// Send SMS to any number of recipients as a stream, until cancelled
+    ///
+    /// Behaves like [`Client::send_sms_stream`], but checks `cancel` before
+    /// each chunk and while waiting for send quota, like
+    /// [`Client::send_sms_all_cancellable`]. A cancelled batch ends with a
+    /// [`BatchEvent::Stopped`] that has
+    /// [`BatchStop::cancelled`](crate::sms::BatchStop::cancelled) set.
+    pub fn send_sms_stream_cancellable(
+        &self,
+        request: SendSmsRequest,
+        options: &BatchOptions,
+        cancel: &CancellationToken,
+    ) -> impl Stream<Item = BatchEvent> + '_ {
+        self.batch_stream(BatchRun::new(request, options), cancel.clone())
+    }
+
+    /// Turn a chunked send into a stream of its events
+    fn batch_stream(
+        &self,
+        run: BatchRun,
+        cancel: CancellationToken,
+    ) -> impl Stream<Item = BatchEvent> + '_ {
+        stream::unfold((run, cancel), move |(mut run, cancel)| async move {
+            let event = self.next_batch_event(&mut run, &cancel).await?;
+            Some((event, (run, cancel)))
+        })
+    }
+
+    /// Send SMS message and return the response with transport metadata
+    ///
+    /// Behaves like [`Client::send_sms`], but also returns any rate-limit hints
+    /// found in the response headers so adaptive senders can back off early.
+    pub async fn send_sms_raw(
+        &self,
+        request: SendSmsRequest,
+    ) -> Result<RawResponse<SendSmsResponse>> {
+        self.check_category_policy(&request)?;
+        self.startup_delay().await;
+        if self.profile.is_explicit_nulls() {
+            self.make_raw_request("SendSms", &request.to_json_with_nulls())
+                .await
+        } else {
+            self.make_raw_request("SendSms", &request).await
+        }
+    }
+
+    /// Wait out the profile's startup jitter, once per client
+    async fn startup_delay(&self) {
+        let Some(max) = self.profile.get_startup_jitter() else {
+            return;
+        };
+        self.startup
+            .get_or_init(|| async {
+                let seed = self.profile.startup_jitter_seed.unwrap_or_else(time_seed);
+                let max_millis = max.as_millis() as u64;
+                let delay = Duration::from_millis(splitmix64(seed) % (max_millis + 1));
+                log::debug!("Delaying first send by {:?}", delay);
+                tokio::time::sleep(delay).await;
+            })
+            .await;
+    }
+
+    /// Let the profile's category policy veto a categorized send
+    fn check_category_policy(&self, request: &SendSmsRequest) -> Result<()> {
+        match (request.category, self.profile.get_category_policy()) {
+            (Some(category), Some(policy)) => policy
+                .check(category, request)
+                .map_err(TencentCloudError::policy),
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply for a new SMS signature
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - AddSmsSignRequest describing the signature and its documents
+    ///
+    /// The request is validated locally first, see [`AddSmsSignRequest::validate`].
+    pub async fn add_sms_sign(&self, request: AddSmsSignRequest) -> Result<AddSmsSignResponse> {
+        request.validate().map_err(TencentCloudError::parameter)?;
+        self.make_request("AddSmsSign", &request).await
+    }
+
+    /// Query the review status of SMS signatures
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - DescribeSmsSignListRequest containing the signature IDs
+    pub async fn describe_sms_sign_list(
+        &self,
+        request: DescribeSmsSignListRequest,
+    ) -> Result<DescribeSmsSignListResponse> {
+        self.make_request("DescribeSmsSignList", &request).await
+    }
+
+    /// Query signatures and keep only those with the given review status
+    ///
+    /// The API only describes signatures by ID, so the IDs to reconcile must be
+    /// passed in; filtering by status happens client-side.
+    ///
+    /// # Arguments
+    ///
+    /// * `sign_ids` - IDs of the signatures to query
+    /// * `international` - Whether the signatures are for international SMS
+    /// * `status` - Review status to keep
+    pub async fn describe_signs_by_status(
+        &self,
+        sign_ids: Vec<u64>,
+        international: bool,
+        status: SignStatus,
+    ) -> Result<Vec<DescribeSignListStatus>> {
+        let response = self
+            .describe_sms_sign_list(DescribeSmsSignListRequest::new(sign_ids, international))
+            .await?;
+        Ok(response
+            .describe_sign_list_status_set
+            .into_iter()
+            .filter(|sign| sign.status() == Some(status))
+            .collect())
+    }
+
+    /// Query the review status of SMS templates
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - DescribeSmsTemplateListRequest containing the template IDs or page
+    pub async fn describe_sms_template_list(
+        &self,
+        request: DescribeSmsTemplateListRequest,
+    ) -> Result<DescribeSmsTemplateListResponse> {
+        self.make_request("DescribeSmsTemplateList", &request).await
+    }
+
+    /// Check that the credentials are accepted by the API, without sending SMS
+    ///
+    /// Issues a DescribeSmsTemplateList call for a single domestic template.
+    /// `AuthFailure` API errors are returned as [`TencentCloudError::Auth`];
+    /// any other API response, successful or not, means the credentials were
+    /// accepted. Transport errors are returned unchanged, since they say
+    /// nothing about the credentials.
+    pub async fn verify_credentials(&self) -> Result<()> {
+        match self
+            .describe_sms_template_list(DescribeSmsTemplateListRequest::page(false, 1, 0))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(TencentCloudError::Api { code, message, .. }) => {
+                if code.starts_with("AuthFailure") {
+                    Err(TencentCloudError::auth(format!("{}: {}", code, message)))
+                } else {
+                    Ok(())
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Validate the configuration and warm the connection before the first send
+    ///
+    /// Checks the region, credential and headers locally and derives today's
+    /// signing key into the cache (see [`Client::signing_cache_stats`]), then
+    /// issues the same cheap DescribeSmsTemplateList call as
+    /// [`Client::verify_credentials`]. That call resolves the endpoint and
+    /// opens a pooled connection (DNS and TLS handshake), so configuration
+    /// problems surface at startup instead of on the first real send.
+    pub async fn warm_up(&self) -> Result<()> {
+        validate_region(&self.region)?;
+        self.credential.validate()?;
+        self.profile.validate_headers()?;
+        self.signer.prepare(self.profile.signing_time().timestamp());
+        log::debug!(
+            "Warming up client for {}",
+            self.profile
+                .resolve_endpoint(&self.region, "DescribeSmsTemplateList")
+        );
+        self.verify_credentials().await
+    }
+
+    /// Pull the delivery receipts of a phone number
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - PullSmsSendStatusByPhoneNumberRequest with the phone number and time range
+    pub async fn pull_sms_send_status_by_phone_number(
+        &self,
+        request: PullSmsSendStatusByPhoneNumberRequest,
+    ) -> Result<PullSmsSendStatusByPhoneNumberResponse> {
+        self.make_request("PullSmsSendStatusByPhoneNumber", &request)
+            .await
+    }
+
+    /// Send SMS and poll delivery receipts until every message is delivered or failed
+    ///
+    /// After sending, the receipts of each accepted number are pulled every
+    /// `poll_interval` until each reports a final status or `timeout` elapses
+    /// since the send. Numbers whose send failed are reported as
+    /// [`DeliveryState::NotSent`], and numbers without a receipt at the
+    /// timeout as [`DeliveryState::Pending`].
+    ///
+    /// Polls wait until the reset time when the API reports no remaining
+    /// requests in the rate-limit headers, and a throttled or otherwise
+    /// retryable poll is simply tried again at the next interval.
+    pub async fn send_and_await_delivery(
+        &self,
+        request: SendSmsRequest,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<DeliveryResult> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let sms_sdk_app_id = request.sms_sdk_app_id.clone();
+        // Receipts carry the delivery time, so start the range a little early
+        // to tolerate clock skew between this host and the API
+        let begin_time = (self.profile.get_clock().now().timestamp() - 60).max(0) as u64;
+
+        let response = self.send_sms(request).await?;
+        let mut reports: Vec<DeliveryReport> = response
+            .send_status_set
+            .iter()
+            .map(|status| DeliveryReport {
+                phone_number: status.phone_number.clone(),
+                serial_no: status.serial_no.clone(),
+                state: if status.is_success() {
+                    DeliveryState::Pending
+                } else {
+                    DeliveryState::NotSent
+                },
+                receipt: None,
+            })
+            .collect();
+
+        'poll: while reports
+            .iter()
+            .any(|report| report.state == DeliveryState::Pending)
+        {
+            let mut wake = tokio::time::Instant::now() + poll_interval;
+            if wake > deadline {
+                break;
+            }
+            tokio::time::sleep_until(wake).await;
+
+            for index in 0..reports.len() {
+                if reports[index].state != DeliveryState::Pending {
+                    continue;
+                }
+                let pull = PullSmsSendStatusByPhoneNumberRequest::new(
+                    reports[index].phone_number.clone(),
+                    sms_sdk_app_id.clone(),
+                    begin_time,
+                );
+                let raw: RawResponse<PullSmsSendStatusByPhoneNumberResponse> = match self
+                    .make_raw_request("PullSmsSendStatusByPhoneNumber", &pull)
+                    .await
+                {
+                    Ok(raw) => raw,
+                    Err(e) if e.is_retryable() => continue 'poll,
+                    Err(e) => return Err(e),
+                };
+
+                for receipt in raw.response.pull_sms_send_status_set {
+                    if let Some(report) = reports
+                        .iter_mut()
+                        .find(|report| report.serial_no == receipt.serial_no)
+                    {
+                        report.state = if receipt.is_delivered() {
+                            DeliveryState::Delivered
+                        } else {
+                            DeliveryState::Failed
+                        };
+                        report.receipt = Some(receipt);
+                    }
+                }
+
+                // Respect the rate limit before the next poll
+                if raw.rate_limit.remaining == Some(0) {
+                    if let Some(reset_at) = raw.rate_limit.reset_at {
+                        let wait =
+                            (reset_at - self.profile.get_clock().now().timestamp()).max(0) as u64;
+                        wake = tokio::time::Instant::now() + Duration::from_secs(wait);
+                        if wake > deadline {
+                            break 'poll;
+                        }
+                        tokio::time::sleep_until(wake).await;
+                    }
+                }
+            }
+        }
+
+        Ok(DeliveryResult { response, reports })
+    }
+
+    /// Call an arbitrary SMS API action with caller-defined request and response types
+    ///
+    /// This exposes the same signing, transport and error handling used by
+    /// [`Client::send_sms`] for actions the crate does not model yet.
+    ///
+    /// * `action` is the API action name exactly as documented by TencentCloud,
+    ///   e.g. `"DescribeSmsTemplateList"`, and is sent as `X-TC-Action`.
+    /// * The request is serialized to JSON as the request body, so its fields
+    ///   should be renamed to the PascalCase parameter names of the action.
+    /// * The response type is deserialized from the contents of the `Response`
+    ///   envelope, i.e. it should contain the action's output fields (and
+    ///   `RequestId` if wanted), not the envelope itself.
+    /// * The API version comes from [`ClientProfile::get_api_version`]; actions
+    ///   that only exist in another version need a profile with that version set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use serde::{Deserialize, Serialize};
+    /// use tencentcloud_sms_sdk::{Client, Credential};
+    ///
+    /// #[derive(Serialize)]
+    /// struct DescribeTemplatesRequest {
+    ///     #[serde(rename = "International")]
+    ///     international: u64,
+    ///     #[serde(rename = "Limit")]
+    ///     limit: u64,
+    ///     #[serde(rename = "Offset")]
+    ///     offset: u64,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct DescribeTemplatesResponse {
+    ///     #[serde(rename = "RequestId")]
+    ///     request_id: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let credential = Credential::new("your_secret_id", "your_secret_key", None);
+    ///     let client = Client::new(credential, "ap-guangzhou");
+    ///
+    ///     let request = DescribeTemplatesRequest { international: 0, limit: 10, offset: 0 };
+    ///     let response: DescribeTemplatesResponse =
+    ///         client.call("DescribeSmsTemplateList", &request).await?;
+    ///     println!("Request ID: {}", response.request_id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn call<T, R>(&self, action: &str, request: &T) -> Result<R>
+    where
+        T: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        self.make_request(action, request).await
     }
 
     /// Make an API request
@@ -149,139 +845,427 @@ impl Client {
         T: serde::Serialize,
         R: serde::de::DeserializeOwned,
     {
-        // Validate credentials
+        self.make_raw_request(action, request)
+            .await
+            .map(|raw| raw.response)
+    }
+
+    /// Make an API request, keeping transport metadata alongside the response
+    async fn make_raw_request<T, R>(&self, action: &str, request: &T) -> Result<RawResponse<R>>
+    where
+        T: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let _in_flight = self
+            .in_flight
+            .enter()
+            .ok_or_else(|| TencentCloudError::other("Client has been shut down"))?;
+        let _permit = match &self.profile.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(|_| TencentCloudError::other("Concurrency limiter closed"))?,
+            ),
+            None => None,
+        };
+
+        // Report the host the request actually goes to, which an endpoint
+        // resolver may pick per action
+        let endpoint = self.profile.resolve_endpoint(&self.region, action);
+        let result = telemetry::instrument(
+            self.execute(action, request),
+            &self.service,
+            action,
+            endpoint_host(&endpoint),
+        )
+        .await;
+
+        // Final scrubbing pass, in case a misconfigured proxy or endpoint echoes
+        // the credentials back in an error message
+        result.map_err(|e| {
+            e.redact(&[
+                self.credential.secret_key(),
+                self.credential.token().unwrap_or_default(),
+            ])
+        })
+    }
+
+    /// Validate, serialize and send a request, retrying per the retry profile
+    async fn execute<T, R>(&self, action: &str, request: &T) -> Result<RawResponse<R>>
+    where
+        T: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        // Validate credentials and headers
         self.credential.validate()?;
+        self.profile.validate_headers()?;
 
         // Serialize request body
         let payload = serde_json::to_string(request)?;
 
-        // Current timestamp
-        let timestamp = Utc::now();
-
-        // Build headers
-        let mut headers = HashMap::new();
-        headers.insert("Content-Type".to_string(), "application/json".to_string());
-        headers.insert(
-            "Host".to_string(),
-            self.profile.get_http_profile().endpoint.clone(),
-        );
-        headers.insert("X-TC-Action".to_string(), action.to_string());
-        headers.insert(
-            "X-TC-Version".to_string(),
-            self.profile.get_api_version().to_string(),
-        );
-        headers.insert("X-TC-Region".to_string(), self.region.clone());
-        headers.insert(
-            "X-TC-Timestamp".to_string(),
-            timestamp.timestamp().to_string(),
-        );
-        headers.insert(
-            "X-TC-Language".to_string(),
-            self.profile.get_language().to_string(),
-        );
-
-        // Add session token if available
-        if let Some(token) = self.credential.token() {
-            headers.insert("X-TC-Token".to_string(), token.to_string());
+        // Cancellation safety: nothing below holds a lock, permit or other
+        // shared state across an await, so dropping this future at any point
+        // leaves the client untouched.
+        match self.profile.get_retry_profile().get_total_timeout() {
+            Some(limit) => tokio::time::timeout(limit, self.send_with_retries(action, payload))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(TencentCloudError::timeout(format!(
+                        "{} did not complete within {:?}",
+                        action, limit
+                    )))
+                }),
+            None => self.send_with_retries(action, payload).await,
         }
+    }
 
-        // Prepare headers for signing
-        let host = self.profile.get_http_profile().endpoint.clone();
-        let canonical_headers = format!("content-type:application/json\nhost:{}\n", host);
-        let signed_headers = "content-type;host";
-        let hashed_payload = sha256_hex(&payload);
+    /// Send a serialized request, retrying per the profile's retry settings
+    async fn send_with_retries<R>(&self, action: &str, payload: String) -> Result<RawResponse<R>>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let retry = self.profile.get_retry_profile();
+        let mut attempt = 0;
+        loop {
+            let result = match retry.get_per_attempt_timeout() {
+                Some(limit) => tokio::time::timeout(limit, self.send_once(action, payload.clone()))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(TencentCloudError::timeout(format!(
+                            "{} attempt timed out after {:?}",
+                            action, limit
+                        )))
+                    }),
+                None => self.send_once(action, payload.clone()).await,
+            };
+            match result {
+                Err(e) if attempt < retry.get_max_retries() && e.is_retryable() => {
+                    attempt += 1;
+                    let delay = retry.get_backoff().next_delay(attempt);
+                    log::debug!(
+                        "Retrying {} (attempt {}) in {:?}: {}",
+                        action,
+                        attempt,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
 
-        // Sign the request using TC3 signer
-        let result = self.signer.sign(
-            &self.profile.get_http_profile().req_method,
-            "/",
-            "",
-            &canonical_headers,
-            signed_headers,
-            &hashed_payload,
-            timestamp.timestamp(),
-        );
+    /// Sign and send a single attempt of an API request
+    async fn send_once<R>(&self, action: &str, payload: String) -> Result<RawResponse<R>>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        // Current timestamp, taken per attempt so retries carry a fresh signature
+        let timestamp = self.profile.signing_time();
 
-        // Create authorization header
-        let authorization = self
-            .signer
-            .create_authorization_header(&result, signed_headers);
-        headers.insert("Authorization".to_string(), authorization);
+        // Build and sign the request
+        let signed = self.sign_request(action, payload, timestamp.timestamp());
 
         // Build HTTP request
-        let url = self.profile.get_http_profile().get_full_endpoint();
-        let mut request_builder = match self.profile.get_http_profile().req_method.as_str() {
-            "GET" => self.http_client.get(&url),
-            "POST" => self.http_client.post(&url),
-            _ => self.http_client.post(&url),
+        let mut request_builder = match signed.method.as_str() {
+            "GET" => self.transport.client().get(&signed.url),
+            "POST" => self.transport.client().post(&signed.url),
+            _ => self.transport.client().post(&signed.url),
         };
 
         // Add headers
-        for (key, value) in headers {
-            request_builder = request_builder.header(&key, &value);
+        for (key, value) in &signed.headers {
+            request_builder = request_builder.header(key, value);
         }
 
         // Add body for POST requests
-        if self.profile.get_http_profile().req_method == "POST" {
-            request_builder = request_builder.body(payload.clone());
+        if signed.method == "POST" {
+            request_builder = request_builder.body(signed.body.clone());
+        }
+
+        // Per-action timeouts replace the HTTP client's request timeout
+        if let Some(timeout) = self.profile.action_timeouts.get(action) {
+            request_builder = request_builder.timeout(*timeout);
         }
 
         // Send request
+        self.transport.record_request(&signed.url);
         let response = request_builder.send().await?;
 
-        // Check status code
-        if !response.status().is_success() {
-            return Err(TencentCloudError::other(format!(
-                "HTTP error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )));
+        // Check status code, preferring the API error envelope of the body
+        let status = response.status();
+        if !status.is_success() {
+            let body = decode_utf8(&response.bytes().await?)?;
+            return Err(
+                TencentCloudError::from_response_json(&body).unwrap_or_else(|| {
+                    TencentCloudError::other(format!("HTTP error: {} - {}", status, body))
+                }),
+            );
         }
 
-        // Get response text
-        let response_text = response.text().await?;
+        // Capture rate-limit hints before the body consumes the response
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+
+        // Get response text, refusing to paper over invalid UTF-8
+        let response_text = decode_utf8(&response.bytes().await?)?;
 
         // Debug logging
         if self.profile.is_debug() {
-            log::debug!("Request: {}", payload);
+            log::debug!("Request: {}", signed.body);
             log::debug!("Response: {}", response_text);
         }
 
         // Parse response
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)?;
+        let response_json: serde_json::Value =
+            serde_json::from_str(&response_text).map_err(|e| {
+                TencentCloudError::other(format!(
+                    "Invalid response format: {}; body: {}",
+                    e,
+                    body_snippet(&response_text)
+                ))
+            })?;
+
+        let request_id = response_json
+            .get("Response")
+            .and_then(|r| r.get("RequestId"))
+            .and_then(|r| r.as_str())
+            .map(RequestId::from);
+        if let Some(request_id) = &request_id {
+            telemetry::record_request_id(request_id.as_str());
+        }
 
         // Check for API errors
-        if let Some(error) = response_json.get("Response").and_then(|r| r.get("Error")) {
-            let code = error
-                .get("Code")
-                .and_then(|c| c.as_str())
-                .unwrap_or("Unknown");
-            let message = error
-                .get("Message")
-                .and_then(|m| m.as_str())
-                .unwrap_or("Unknown error");
-            let request_id = response_json
-                .get("Response")
-                .and_then(|r| r.get("RequestId"))
-                .and_then(|r| r.as_str())
-                .map(|s| s.to_string());
-
-            return Err(TencentCloudError::api_with_request_id(
-                code,
-                message,
-                request_id.as_deref(),
-            ));
+        if let Some(error) = TencentCloudError::from_response_value(&response_json) {
+            return Err(error);
         }
 
         // Extract the actual response data
-        let response_data = response_json
-            .get("Response")
-            .ok_or_else(|| TencentCloudError::other("Invalid response format"))?;
+        let response_data = response_json.get("Response").ok_or_else(|| {
+            TencentCloudError::other(format!(
+                "Invalid response format: missing Response envelope; body: {}",
+                body_snippet(&response_text)
+            ))
+        })?;
 
-        // Deserialize response
-        let result: R = serde_json::from_value(response_data.clone())?;
+        // Deserialize response; a mismatch here means the API returned a
+        // shape this SDK does not expect, so quote the body instead of only
+        // the serde message
+        let result: R = serde_json::from_value(response_data.clone()).map_err(|e| {
+            TencentCloudError::other(format!(
+                "Response schema mismatch for {} ({}); the API response shape may have changed; Response: {}",
+                action,
+                e,
+                body_snippet(&response_data.to_string())
+            ))
+        })?;
 
-        Ok(result)
+        Ok(RawResponse {
+            response: result,
+            rate_limit,
+            timestamp: timestamp.timestamp(),
+            request_id,
+        })
+    }
+
+    /// Serialize and sign a request without sending it, returning the headers
+    ///
+    /// This is the signing path used by every API call, exposed so benchmarks
+    /// can measure it without network access. It is not part of the stable API.
+    #[doc(hidden)]
+    pub fn sign_headers<T: serde::Serialize>(
+        &self,
+        action: &str,
+        request: &T,
+        timestamp: i64,
+    ) -> Result<Vec<(String, String)>> {
+        self.profile.validate_headers()?;
+        let payload = serde_json::to_string(request)?;
+        Ok(self.sign_request(action, payload, timestamp).headers)
+    }
+
+    /// Build the headers for an API request and sign them
+    fn sign_request(&self, action: &str, payload: String, timestamp: i64) -> SignedRequest {
+        let http_profile = self.profile.get_http_profile();
+        let endpoint = self.profile.resolve_endpoint(&self.region, action);
+        let host = endpoint_host(&endpoint);
+
+        // Build headers
+        let mut headers = vec![
+            (
+                "Content-Type".to_string(),
+                self.profile.get_content_type().to_string(),
+            ),
+            ("Host".to_string(), host.to_string()),
+            ("X-TC-Action".to_string(), action.to_string()),
+            (
+                "X-TC-Version".to_string(),
+                self.profile.get_api_version().to_string(),
+            ),
+            ("X-TC-Region".to_string(), self.region.clone()),
+            ("X-TC-Timestamp".to_string(), timestamp.to_string()),
+            (
+                "X-TC-Language".to_string(),
+                self.profile.get_language().to_string(),
+            ),
+        ];
+
+        if let Some(accept) = self.profile.get_accept() {
+            headers.push(("Accept".to_string(), accept.to_string()));
+        }
+
+        // Add session token if available. Per the TC3-HMAC-SHA256 spec only
+        // content-type and host must be signed; X-TC-Token is sent alongside
+        // the signature but is not part of SignedHeaders, matching the
+        // official SDKs.
+        if let Some(token) = self.credential.token() {
+            headers.push(("X-TC-Token".to_string(), token.to_string()));
+        }
+
+        if self.profile.is_unsigned_payload() {
+            headers.push((
+                "X-TC-Content-SHA256".to_string(),
+                UNSIGNED_PAYLOAD.to_string(),
+            ));
+        }
+
+        headers.extend(self.profile.get_headers().iter().cloned());
+
+        // Prepare headers for signing
+        let (canonical_headers, signed_headers) = canonical_headers(
+            self.profile.get_content_type(),
+            host,
+            self.profile.get_headers(),
+        );
+        let signed_payload = self.signed_payload(&payload);
+        let hashed_payload = sha256_hex(signed_payload);
+        if self.profile.is_debug() {
+            log::debug!(
+                "Hashed payload: SHA-256 {} of {} bytes: {}",
+                hashed_payload,
+                signed_payload.len(),
+                signed_payload
+            );
+        }
+
+        // Sign the request using TC3 signer
+        let result = self.signer.sign(
+            &http_profile.req_method,
+            &canonical_headers,
+            &signed_headers,
+            &hashed_payload,
+            timestamp,
+            &self.region,
+        );
+
+        if self.profile.is_debug() {
+            log::debug!("Credential scope: {}", result.credential_scope);
+            log::debug!("Canonical request: {:?}", result.canonical_request);
+            log::debug!("String to sign: {:?}", result.string_to_sign);
+        }
+
+        // Create authorization header
+        let authorization = self.signer.authorization(&result, &signed_headers);
+        headers.push(("Authorization".to_string(), authorization));
+
+        SignedRequest {
+            method: http_profile.req_method.clone(),
+            url: http_profile.full_endpoint_for(&endpoint),
+            headers,
+            body: payload,
+        }
+    }
+
+    /// Get the bytes hashed into the signature for a request body
+    fn signed_payload<'a>(&self, payload: &'a str) -> &'a str {
+        if self.profile.is_unsigned_payload() {
+            UNSIGNED_PAYLOAD
+        } else {
+            payload
+        }
+    }
+
+    /// Get the exact bytes hashed into the signature and their SHA-256
+    ///
+    /// The bytes are the JSON body as sent, or `UNSIGNED-PAYLOAD` with
+    /// [`ClientProfile::set_unsigned_payload`]. Comparing the hash with one
+    /// computed over the body the server received pinpoints payload-related
+    /// signature mismatches. With debug mode on, the same is logged for every
+    /// signed request.
+    #[cfg(feature = "debug-tools")]
+    pub fn describe_hashed_payload<T: serde::Serialize>(
+        &self,
+        request: &T,
+    ) -> Result<(String, String)> {
+        let payload = serde_json::to_string(request)?;
+        let signed_payload = self.signed_payload(&payload);
+        Ok((signed_payload.to_string(), sha256_hex(signed_payload)))
+    }
+
+    /// Render an API request as an equivalent curl command
+    ///
+    /// The headers are built and signed exactly as `make_request` would, but the
+    /// signature and session token are replaced with `REDACTED`, so the output is
+    /// safe to paste into support tickets. Because the signature is bound to the
+    /// request timestamp, the command is meant for inspection and will not
+    /// succeed if replayed as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::{Client, Credential, SendSmsRequest};
+    ///
+    /// let credential = Credential::new("your_secret_id", "your_secret_key", None);
+    /// let client = Client::new(credential, "ap-guangzhou");
+    /// let request = SendSmsRequest::new(
+    ///     vec!["+8613800000000".to_string()],
+    ///     "1400000000",
+    ///     "123456",
+    ///     "YourSignature",
+    ///     vec!["123456".to_string()],
+    /// );
+    ///
+    /// let curl = client.to_curl("SendSms", &request).unwrap();
+    /// println!("{}", curl);
+    /// ```
+    #[cfg(feature = "debug-tools")]
+    pub fn to_curl<T: serde::Serialize>(&self, action: &str, request: &T) -> Result<String> {
+        self.profile.validate_headers()?;
+        let payload = serde_json::to_string(request)?;
+        let signed = self.sign_request(action, payload, self.profile.signing_time().timestamp());
+
+        let mut command = format!("curl -X {} {}", signed.method, signed.url);
+        for (key, value) in redacted_headers(&signed) {
+            command.push_str(&format!(
+                " -H {}",
+                shell_quote(&format!("{}: {}", key, value))
+            ));
+        }
+        if signed.method == "POST" {
+            command.push_str(&format!(" -d {}", shell_quote(&signed.body)));
+        }
+
+        Ok(command)
+    }
+
+    /// Get the headers that would be sent for an API request, without secrets
+    ///
+    /// The headers are built and signed exactly as `make_request` would at
+    /// `timestamp`, with the signature and session token replaced by
+    /// `REDACTED` as in [`Client::to_curl`], for comparing header construction
+    /// against the API documentation.
+    #[cfg(feature = "debug-tools")]
+    pub fn describe_request_headers<T: serde::Serialize>(
+        &self,
+        action: &str,
+        request: &T,
+        timestamp: i64,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        self.profile.validate_headers()?;
+        let payload = serde_json::to_string(request)?;
+        let signed = self.sign_request(action, payload, timestamp);
+        Ok(redacted_headers(&signed).into_iter().collect())
     }
 
     /// Get the region
@@ -299,38 +1283,227 @@ impl Client {
         &self.profile
     }
 
+    /// Get the signing key cache statistics as `(hits, misses)`
+    ///
+    /// The TC3 signing key is derived once per UTC date and cached, so a
+    /// long-running client should see one miss per day and a hit for every
+    /// other request. The counters are shared by clones and start over when
+    /// the credentials change.
+    pub fn signing_cache_stats(&self) -> (u64, u64) {
+        self.signer.cache_stats()
+    }
+
+    /// Check whether this client shares its HTTP transport with another client
+    pub fn shares_transport_with(&self, other: &Client) -> bool {
+        Arc::ptr_eq(&self.transport, &other.transport)
+    }
+
+    /// Get the connection reuse statistics of the HTTP transport
+    ///
+    /// Counts every request sent and every new connection opened, so a client
+    /// held for the process lifetime should show far fewer connections than
+    /// requests. The counters are shared by clones and by clients of the same
+    /// [`ClientPool`](crate::core::ClientPool).
+    ///
+    /// New connections are counted by the DNS lookup that opens them, so the
+    /// statistics are only available while the transport resolves endpoint
+    /// names itself. Returns `None` for clients built around a caller-supplied
+    /// HTTP client, for clients sending through a proxy, and once a request
+    /// went to an endpoint given as an IP address.
+    pub fn connection_pool_stats(&self) -> Option<ConnectionPoolStats> {
+        self.transport.stats()
+    }
+
     /// Set a new region
     pub fn set_region<S: Into<String>>(&mut self, region: S) {
         self.region = region.into();
     }
 
+    /// Set a new region, rejecting a malformed one
+    pub fn try_set_region(&mut self, region: &str) -> Result<()> {
+        validate_region(region)?;
+        self.region = region.to_string();
+        Ok(())
+    }
+
     /// Update the client profile
     pub fn set_profile(&mut self, profile: ClientProfile) {
-        self.profile = profile.clone();
-        // Update signer with new debug setting
-        self.signer = Tc3Signer::new(
-            self.credential.secret_id().to_string(),
-            self.credential.secret_key().to_string(),
-            "sms".to_string(),
-            profile.is_debug(),
-        );
+        self.signer = build_signer(&self.credential, &profile);
+        self.profile = profile;
     }
 
     /// Update credentials
     pub fn set_credential(&mut self, credential: Credential) {
-        self.credential = credential.clone();
-        self.signer = Tc3Signer::new(
-            credential.secret_id().to_string(),
-            credential.secret_key().to_string(),
-            "sms".to_string(),
-            self.profile.is_debug(),
-        );
+        self.signer = build_signer(&credential, &self.profile);
+        self.credential = credential;
+    }
+
+    /// Replace the credentials, region and profile in one step
+    ///
+    /// Intended for configuration reloads. The signer is rebuilt once and the
+    /// HTTP transport is rebuilt from the new profile, so changed timeouts and
+    /// proxy settings take effect; this also detaches the client from any
+    /// transport it shared with other clients.
+    pub fn reconfigure<S: Into<String>>(
+        &mut self,
+        credential: Credential,
+        region: S,
+        profile: ClientProfile,
+    ) {
+        let in_flight = self.in_flight.clone();
+        let startup = self.startup.clone();
+        *self = Self::with_profile(credential, region, profile);
+        self.in_flight = in_flight;
+        self.startup = startup;
+    }
+
+    /// Stop accepting requests and wait for running ones to finish
+    ///
+    /// Calls made after shutdown starts fail immediately. Returns once every
+    /// running call has completed, or a timeout error if some are still
+    /// running after `timeout`; they are not cancelled. Calling it again is
+    /// harmless. The connection pool is released when the client, and every
+    /// client sharing its transport, is dropped.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<()> {
+        match self.in_flight.drain(timeout).await {
+            0 => Ok(()),
+            running => Err(TencentCloudError::timeout(format!(
+                "{} requests still in flight after {:?}",
+                running, timeout
+            ))),
+        }
     }
 }
 
+/// Check that a region matches `^[a-z]+-[a-z]+(-[a-z0-9]+)?$`
+fn validate_region(region: &str) -> Result<()> {
+    let parts: Vec<&str> = region.split('-').collect();
+    let is_alpha = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase());
+    let valid = match parts.as_slice() {
+        [area, city] => is_alpha(area) && is_alpha(city),
+        [area, city, suffix] => {
+            is_alpha(area)
+                && is_alpha(city)
+                && !suffix.is_empty()
+                && suffix
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        }
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(TencentCloudError::config(format!(
+            "Invalid region '{}': expected a form like 'ap-guangzhou'",
+            region
+        )))
+    }
+}
+
+/// Stand-in for the body hash when the payload is left unsigned
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Maximum number of characters of a raw response body quoted in errors
+const BODY_SNIPPET_CHARS: usize = 500;
+
+/// Get the leading part of a response body for error messages
+fn body_snippet(body: &str) -> String {
+    match body.char_indices().nth(BODY_SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+/// Decode a response body as strict UTF-8
+///
+/// Lossy decoding would silently replace the bytes of a mis-encoded message,
+/// e.g. a GBK-encoded Chinese error, with replacement characters.
+fn decode_utf8(body: &[u8]) -> Result<String> {
+    String::from_utf8(body.to_vec()).map_err(|e| {
+        TencentCloudError::other(format!(
+            "Response body is not valid UTF-8 (invalid byte at offset {} of {}); the server or a proxy may have used another encoding",
+            e.utf8_error().valid_up_to(),
+            body.len()
+        ))
+    })
+}
+
+/// Build the canonical headers and signed header list for TC3-HMAC-SHA256
+///
+/// Content-Type and Host are always signed, along with any `X-TC-*` header in
+/// `extra`. Names are lowercased and sorted, and values trimmed and lowercased,
+/// as the spec requires, so each line always matches the header actually sent.
+fn canonical_headers(
+    content_type: &str,
+    host: &str,
+    extra: &[(String, String)],
+) -> (String, String) {
+    let mut signed: Vec<(String, String)> = [("content-type", content_type), ("host", host)]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.trim().to_lowercase()))
+        .chain(
+            extra
+                .iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value.trim().to_lowercase()))
+                .filter(|(name, _)| name.starts_with("x-tc-")),
+        )
+        .collect();
+    signed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let canonical = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let names: Vec<&str> = signed.iter().map(|(name, _)| name.as_str()).collect();
+    (canonical, names.join(";"))
+}
+
+/// Create the request signer for a set of credentials and profile
+fn build_signer(credential: &Credential, profile: &ClientProfile) -> Arc<Signer> {
+    let mut signer = Signer::new(credential.secret_id(), credential.secret_key(), "sms");
+    signer
+        .set_algorithm(profile.get_signature_algorithm())
+        .set_scope_format(profile.get_scope_format());
+    Arc::new(signer)
+}
+
+/// Replace the signature in an Authorization header with `REDACTED`
+#[cfg(feature = "debug-tools")]
+fn redact_signature(authorization: &str) -> String {
+    match authorization.find("Signature=") {
+        Some(pos) => format!("{}Signature=REDACTED", &authorization[..pos]),
+        None => "REDACTED".to_string(),
+    }
+}
+
+/// Get the headers of a signed request with the signature and token redacted
+#[cfg(feature = "debug-tools")]
+fn redacted_headers(signed: &SignedRequest) -> Vec<(String, String)> {
+    signed
+        .headers
+        .iter()
+        .map(|(key, value)| {
+            let value = match key.as_str() {
+                "Authorization" => redact_signature(value),
+                "X-TC-Token" => "REDACTED".to_string(),
+                _ => value.clone(),
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+/// Quote a string for safe use as a single POSIX shell argument
+#[cfg(feature = "debug-tools")]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::signature::credential_scope;
     use crate::core::HttpProfile;
     use crate::sms::SendSmsRequest;
 
@@ -355,6 +1528,17 @@ mod tests {
         assert_eq!(client.profile().get_http_profile().req_timeout, 30);
     }
 
+    #[test]
+    fn test_region_constructors() {
+        let credential = Credential::new("test_id", "test_key", None);
+        assert_eq!(Client::beijing(credential.clone()).region(), "ap-beijing");
+        assert_eq!(
+            Client::guangzhou(credential.clone()).region(),
+            "ap-guangzhou"
+        );
+        assert_eq!(Client::nanjing(credential).region(), "ap-nanjing");
+    }
+
     #[test]
     fn test_client_setters() {
         let credential = Credential::new("test_id", "test_key", None);
@@ -368,6 +1552,28 @@ mod tests {
         assert_eq!(client.credential.secret_id(), "new_id");
     }
 
+    #[test]
+    fn test_client_reconfigure() {
+        let credential = Credential::new("test_id", "test_key", None);
+        let mut client = Client::new(credential, "ap-guangzhou");
+        let transport = client.transport.clone();
+
+        let mut profile = ClientProfile::new();
+        let mut http_profile = HttpProfile::new();
+        http_profile.set_req_timeout(5);
+        profile.set_http_profile(http_profile);
+        client.reconfigure(
+            Credential::new("new_id", "new_key", None),
+            "ap-beijing",
+            profile,
+        );
+
+        assert_eq!(client.region(), "ap-beijing");
+        assert_eq!(client.credential.secret_id(), "new_id");
+        assert_eq!(client.profile().get_http_profile().req_timeout, 5);
+        assert!(!Arc::ptr_eq(&transport, &client.transport));
+    }
+
     #[tokio::test]
     async fn test_send_sms_invalid_credentials() {
         let credential = Credential::new("", "", None);
@@ -384,4 +1590,349 @@ mod tests {
         let result = client.send_sms(request).await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_region_validation() {
+        for region in [
+            "ap-guangzhou",
+            "na-siliconvalley",
+            "ap-shanghai-fsi",
+            "eu-frankfurt-1",
+        ] {
+            assert!(validate_region(region).is_ok(), "{}", region);
+        }
+        for region in [
+            "ap_guangzhou",
+            "ap",
+            "AP-Guangzhou",
+            "ap-guangzhou-",
+            "-ap",
+            "",
+        ] {
+            assert!(validate_region(region).is_err(), "{}", region);
+        }
+
+        let credential = Credential::new("test_id", "test_key", None);
+        assert!(Client::try_new(credential.clone(), "ap_guangzhou").is_err());
+        let mut client = Client::try_new(credential, "ap-guangzhou").unwrap();
+        assert!(client.try_set_region("ap_beijing").is_err());
+        assert_eq!(client.region(), "ap-guangzhou");
+        client.try_set_region("ap-beijing").unwrap();
+        assert_eq!(client.region(), "ap-beijing");
+    }
+
+    #[test]
+    fn test_credential_scope_date_rolls_over_at_utc_midnight() {
+        use chrono::{TimeZone, Utc};
+
+        let credential = Credential::new("test_id", "test_key", None);
+        let client = Client::new(credential, "ap-guangzhou");
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "Test",
+            vec!["123456".to_string()],
+        );
+
+        for (time, date) in [
+            (
+                Utc.with_ymd_and_hms(2021, 1, 1, 23, 59, 59).unwrap(),
+                "2021-01-01",
+            ),
+            (
+                Utc.with_ymd_and_hms(2021, 1, 2, 0, 0, 1).unwrap(),
+                "2021-01-02",
+            ),
+        ] {
+            let headers = client
+                .sign_headers("SendSms", &request, time.timestamp())
+                .unwrap();
+            let header = |name: &str| {
+                headers
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.as_str())
+                    .unwrap()
+            };
+
+            assert_eq!(header("X-TC-Timestamp"), time.timestamp().to_string());
+            let scope = credential_scope(time, "sms");
+            assert!(scope.starts_with(date));
+            assert!(header("Authorization").contains(&format!("Credential=test_id/{},", scope)));
+        }
+    }
+
+    #[test]
+    fn test_custom_content_type_is_signed() {
+        let credential = Credential::new("test_id", "test_key", None);
+        let mut profile = ClientProfile::new();
+        profile
+            .set_content_type("application/json; charset=UTF-8")
+            .set_accept("application/json");
+        let client = Client::with_profile(credential, "ap-guangzhou", profile);
+
+        let headers = client
+            .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+            .unwrap();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let content_type = header("Content-Type").unwrap();
+        assert_eq!(content_type, "application/json; charset=UTF-8");
+        assert_eq!(header("Accept"), Some("application/json"));
+        assert!(
+            canonical_headers(content_type, "sms.tencentcloudapi.com", &[])
+                .0
+                .starts_with("content-type:application/json; charset=utf-8\n")
+        );
+
+        let mut profile = ClientProfile::new();
+        profile.set_content_type("application/x-www-form-urlencoded");
+        let client = Client::with_profile(
+            Credential::new("test_id", "test_key", None),
+            "ap-guangzhou",
+            profile,
+        );
+        let error = client
+            .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+            .unwrap_err();
+        assert!(matches!(error, TencentCloudError::Config(_)));
+    }
+
+    #[test]
+    fn test_x_tc_extra_headers_are_signed() {
+        let mut profile = ClientProfile::new();
+        profile
+            .add_header("X-TC-TraceId", " Trace-1 ")
+            .add_header("X-Custom", "unsigned");
+        let client = Client::with_profile(
+            Credential::new("test_id", "test_key", None),
+            "ap-guangzhou",
+            profile,
+        );
+
+        let (canonical, signed) = canonical_headers(
+            client.profile().get_content_type(),
+            "sms.tencentcloudapi.com",
+            client.profile().get_headers(),
+        );
+        assert_eq!(
+            canonical,
+            "content-type:application/json\nhost:sms.tencentcloudapi.com\nx-tc-traceid:trace-1\n"
+        );
+        assert_eq!(signed, "content-type;host;x-tc-traceid");
+
+        let headers = client
+            .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+            .unwrap();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(header("X-TC-TraceId"), Some(" Trace-1 "));
+        assert_eq!(header("X-Custom"), Some("unsigned"));
+        assert!(header("Authorization")
+            .unwrap()
+            .contains("SignedHeaders=content-type;host;x-tc-traceid,"));
+    }
+
+    #[test]
+    fn test_cloned_clients_share_signer() {
+        let client = Client::new(Credential::new("test_id", "test_key", None), "ap-guangzhou");
+        let mut clone = client.clone();
+        assert!(Arc::ptr_eq(&client.signer, &clone.signer));
+        assert!(clone.shares_transport_with(&client));
+
+        let sign = |client: &Client| {
+            client
+                .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+                .unwrap()
+        };
+        assert_eq!(sign(&client), sign(&clone));
+
+        // Changing a clone's credentials gives it its own signer
+        clone.set_credential(Credential::new("other_id", "other_key", None));
+        assert!(!Arc::ptr_eq(&client.signer, &clone.signer));
+        assert_ne!(sign(&client), sign(&clone));
+    }
+
+    #[test]
+    fn test_signature_algorithm_override() {
+        let credential = Credential::new("test_id", "test_key", None);
+        let mut client = Client::new(credential, "ap-guangzhou");
+        let authorization = |client: &Client| {
+            let headers = client
+                .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+                .unwrap();
+            headers
+                .into_iter()
+                .find(|(key, _)| key == "Authorization")
+                .unwrap()
+                .1
+        };
+        assert!(authorization(&client).starts_with("TC3-HMAC-SHA256 Credential="));
+
+        let mut profile = client.profile().clone();
+        profile.set_signature_algorithm("TC4-HMAC-SHA512");
+        client.set_profile(profile);
+        assert!(authorization(&client).starts_with("TC4-HMAC-SHA512 Credential="));
+    }
+
+    #[test]
+    fn test_scope_format() {
+        use crate::core::signature::ScopeFormat;
+
+        let credential = Credential::new("test_id", "test_key", None);
+        let mut client = Client::new(credential, "ap-guangzhou");
+        let authorization = |client: &Client| {
+            let headers = client
+                .sign_headers("SendSms", &serde_json::json!({}), 1609459200)
+                .unwrap();
+            headers
+                .into_iter()
+                .find(|(key, _)| key == "Authorization")
+                .unwrap()
+                .1
+        };
+        // SMS signs without the region by default
+        assert!(authorization(&client).contains("Credential=test_id/2021-01-01/sms/tc3_request,"));
+
+        let mut profile = client.profile().clone();
+        profile.set_scope_format(ScopeFormat::RegionService);
+        client.set_profile(profile);
+        assert!(authorization(&client)
+            .contains("Credential=test_id/2021-01-01/ap-guangzhou/sms/tc3_request,"));
+    }
+
+    #[test]
+    fn test_signing_cache_stats() {
+        let client = Client::new(Credential::new("test_id", "test_key", None), "ap-guangzhou");
+        assert_eq!(client.signing_cache_stats(), (0, 0));
+
+        // Two signs on the same UTC date: one miss, then one hit
+        let request = serde_json::json!({});
+        client
+            .sign_headers("SendSms", &request, 1609459200)
+            .unwrap();
+        client
+            .sign_headers("SendSms", &request, 1609502400)
+            .unwrap();
+        assert_eq!(client.signing_cache_stats(), (1, 1));
+        assert_eq!(client.clone().signing_cache_stats(), (1, 1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_startup_jitter_delays_first_send_only() {
+        let mut profile = ClientProfile::new();
+        profile
+            .set_startup_jitter(Duration::from_secs(10))
+            .set_startup_jitter_seed(7);
+        let client = Client::with_profile(
+            Credential::new("test_id", "test_key", None),
+            "ap-guangzhou",
+            profile,
+        );
+
+        let expected = Duration::from_millis(splitmix64(7) % 10_001);
+        let start = tokio::time::Instant::now();
+        client.startup_delay().await;
+        assert_eq!(start.elapsed(), expected);
+        assert!(expected > Duration::ZERO);
+
+        let start = tokio::time::Instant::now();
+        client.clone().startup_delay().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_body_snippet() {
+        assert_eq!(body_snippet("short body"), "short body");
+
+        let long = "错".repeat(BODY_SNIPPET_CHARS + 10);
+        let snippet = body_snippet(&long);
+        assert_eq!(snippet.chars().count(), BODY_SNIPPET_CHARS + 3);
+        assert!(snippet.ends_with("..."));
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn test_to_curl_redacts_signature() {
+        let credential = Credential::new("test_id", "test_key", Some("test_token"));
+        let client = Client::new(credential, "ap-guangzhou");
+
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "Test",
+            vec!["123456".to_string()],
+        );
+
+        let curl = client.to_curl("SendSms", &request).unwrap();
+        assert!(curl.starts_with("curl -X POST https://sms.tencentcloudapi.com"));
+        assert!(curl.contains("-H 'X-TC-Action: SendSms'"));
+        assert!(curl.contains("Signature=REDACTED'"));
+        assert!(curl.contains("-H 'X-TC-Token: REDACTED'"));
+        assert!(!curl.contains("test_token"));
+        assert!(curl.contains(&format!(
+            "-d '{}'",
+            serde_json::to_string(&request).unwrap()
+        )));
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn test_describe_request_headers() {
+        let credential = Credential::new("test_id", "test_key", Some("test_token"));
+        let client = Client::new(credential, "ap-guangzhou");
+
+        let headers = client
+            .describe_request_headers("SendSms", &serde_json::json!({}), 1609459200)
+            .unwrap();
+        assert_eq!(headers["X-TC-Action"], "SendSms");
+        assert_eq!(headers["X-TC-Timestamp"], "1609459200");
+        assert_eq!(headers["X-TC-Token"], "REDACTED");
+        let authorization = &headers["Authorization"];
+        assert!(authorization.starts_with("TC3-HMAC-SHA256 Credential=test_id/"));
+        assert!(authorization.ends_with("Signature=REDACTED"));
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn test_describe_hashed_payload() {
+        use sha2::{Digest, Sha256};
+
+        let credential = Credential::new("test_id", "test_key", None);
+        let mut client = Client::new(credential, "ap-guangzhou");
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "Test",
+            vec!["123456".to_string()],
+        );
+
+        let body = serde_json::to_string(&request).unwrap();
+        let (hashed, digest) = client.describe_hashed_payload(&request).unwrap();
+        assert_eq!(hashed, body);
+        assert_eq!(digest, hex::encode(Sha256::digest(body.as_bytes())));
+
+        let mut profile = client.profile().clone();
+        profile.set_unsigned_payload(true);
+        client.set_profile(profile);
+        let (hashed, digest) = client.describe_hashed_payload(&request).unwrap();
+        assert_eq!(hashed, "UNSIGNED-PAYLOAD");
+        assert_eq!(digest, hex::encode(Sha256::digest(b"UNSIGNED-PAYLOAD")));
+
+        let headers = client
+            .describe_request_headers("SendSms", &request, 1609459200)
+            .unwrap();
+        assert_eq!(headers["X-TC-Content-SHA256"], "UNSIGNED-PAYLOAD");
+    }
 }
@@ -2,9 +2,33 @@
 
 use crate::error::{Result, TencentCloudError};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable overriding the location of the credentials file
+pub const CREDENTIALS_FILE_ENV: &str = "TENCENTCLOUD_CREDENTIALS_FILE";
+
+/// Environment variable selecting the profile of the credentials file
+pub const PROFILE_ENV: &str = "TENCENTCLOUD_PROFILE";
+
+/// Default credentials file, relative to the home directory
+pub const DEFAULT_CREDENTIALS_FILE: &str = ".tencentcloud/credentials";
+
+/// CVM instance metadata path listing the CAM role credentials
+pub const CVM_ROLE_METADATA_URL: &str =
+    "http://metadata.tencentyun.com/latest/meta-data/cam/security-credentials";
+
+/// How long to wait for the CVM metadata service, which is absent off CVM
+const CVM_METADATA_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// TencentCloud credentials for API authentication
-#[derive(Debug, Clone)]
+///
+/// Surrounding whitespace, such as the trailing newline of a secret pasted
+/// from a dashboard or read from a file, is never part of a valid secret and
+/// would only surface as a signature failure, so the constructors and
+/// [`Credential::set_token`] trim it. Credentials built from the public fields
+/// can be cleaned with [`Credential::trimmed`].
+#[derive(Debug, Clone, Default)]
 pub struct Credential {
     /// Secret ID for authentication
     pub secret_id: String,
@@ -36,6 +60,7 @@ impl Credential {
             secret_key: secret_key.into(),
             token: token.map(|t| t.into()),
         }
+        .trimmed()
     }
 
     /// Create credentials from environment variables
@@ -80,7 +105,208 @@ impl Credential {
             secret_id,
             secret_key,
             token,
-        })
+        }
+        .trimmed())
+    }
+
+    /// Create credentials from environment variables with a given prefix
+    ///
+    /// Reads `{prefix}_SECRET_ID`, `{prefix}_SECRET_KEY` and the optional
+    /// `{prefix}_TOKEN`, e.g. `TENCENTCLOUD` or `TC`. Unlike
+    /// [`Credential::from_env`], the prefixes are never mixed.
+    pub fn from_env_prefix(prefix: &str) -> Result<Self> {
+        Self::from_env_prefix_with(&|name| env::var(name).ok(), prefix)
+    }
+
+    fn from_env_prefix_with(env: &dyn Fn(&str) -> Option<String>, prefix: &str) -> Result<Self> {
+        let var = |suffix: &str| {
+            let name = format!("{}_{}", prefix, suffix);
+            env(&name).ok_or_else(|| {
+                TencentCloudError::auth(format!("{} environment variable not found", name))
+            })
+        };
+        Ok(Self::new(
+            var("SECRET_ID")?,
+            var("SECRET_KEY")?,
+            var("TOKEN").ok(),
+        ))
+    }
+
+    /// Load credentials from a profile of an INI-style credentials file
+    ///
+    /// The file holds one `[profile]` section per credential with
+    /// `secret_id`, `secret_key` and an optional `token`:
+    ///
+    /// ```ini
+    /// [default]
+    /// secret_id = your_secret_id
+    /// secret_key = your_secret_key
+    /// ```
+    pub fn from_profile_file<P: AsRef<Path>>(path: P, profile: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TencentCloudError::auth(format!(
+                "Failed to read credentials file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let mut in_profile = false;
+        let mut found = false;
+        let (mut secret_id, mut secret_key, mut token) = (None, None, None);
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_profile = section.trim() == profile;
+                found |= in_profile;
+                continue;
+            }
+            if !in_profile {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = Some(value.trim().to_string());
+                match key.trim() {
+                    "secret_id" => secret_id = value,
+                    "secret_key" => secret_key = value,
+                    "token" => token = value,
+                    _ => {}
+                }
+            }
+        }
+
+        if !found {
+            return Err(TencentCloudError::auth(format!(
+                "Profile [{}] not found in {}",
+                profile,
+                path.display()
+            )));
+        }
+        match (secret_id, secret_key) {
+            (Some(secret_id), Some(secret_key)) => Ok(Self::new(secret_id, secret_key, token)),
+            _ => Err(TencentCloudError::auth(format!(
+                "Profile [{}] in {} needs secret_id and secret_key",
+                profile,
+                path.display()
+            ))),
+        }
+    }
+
+    /// Load credentials from the default credentials file
+    ///
+    /// The file is `$TENCENTCLOUD_CREDENTIALS_FILE`, or
+    /// `~/.tencentcloud/credentials` if unset, and the profile is
+    /// `$TENCENTCLOUD_PROFILE`, or `default` if unset.
+    pub fn from_default_profile() -> Result<Self> {
+        Self::from_default_profile_with(&|name| env::var(name).ok())
+    }
+
+    fn from_default_profile_with(env: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        let path = match env(CREDENTIALS_FILE_ENV) {
+            Some(path) => PathBuf::from(path),
+            None => env("HOME")
+                .or_else(|| env("USERPROFILE"))
+                .map(|home| Path::new(&home).join(DEFAULT_CREDENTIALS_FILE))
+                .ok_or_else(|| {
+                    TencentCloudError::auth("No home directory to find the credentials file in")
+                })?,
+        };
+        let profile = env(PROFILE_ENV).unwrap_or_else(|| "default".to_string());
+        Self::from_profile_file(path, &profile)
+    }
+
+    /// Load the temporary credentials of the CAM role bound to this CVM
+    ///
+    /// Queries the instance metadata service, which only answers on CVM
+    /// instances, so this fails after a short timeout elsewhere.
+    pub async fn from_cvm_role() -> Result<Self> {
+        Self::from_cvm_role_at(CVM_ROLE_METADATA_URL).await
+    }
+
+    async fn from_cvm_role_at(metadata_url: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(CVM_METADATA_TIMEOUT)
+            .build()?;
+        let role = http
+            .get(format!("{}/", metadata_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let role = role.lines().next().unwrap_or_default().trim();
+        if role.is_empty() {
+            return Err(TencentCloudError::auth("No CAM role is bound to this CVM"));
+        }
+
+        let body: serde_json::Value = http
+            .get(format!("{}/{}", metadata_url, role))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let field = |name: &str| body.get(name).and_then(|v| v.as_str());
+        match (field("TmpSecretId"), field("TmpSecretKey")) {
+            (Some(secret_id), Some(secret_key)) => {
+                Ok(Self::new(secret_id, secret_key, field("Token")))
+            }
+            _ => Err(TencentCloudError::auth(format!(
+                "Unexpected credentials for CAM role {}",
+                role
+            ))),
+        }
+    }
+
+    /// Find credentials from the first source that provides them
+    ///
+    /// Tries, in order:
+    ///
+    /// 1. `TENCENTCLOUD_SECRET_ID`, `TENCENTCLOUD_SECRET_KEY` and `TENCENTCLOUD_TOKEN`
+    /// 2. `TC_SECRET_ID`, `TC_SECRET_KEY` and `TC_TOKEN`
+    /// 3. the default credentials file, see [`Credential::from_default_profile`]
+    /// 4. the CAM role of the CVM instance, see [`Credential::from_cvm_role`]
+    ///
+    /// If every source fails, the returned auth error lists why each did.
+    pub async fn resolve() -> Result<Self> {
+        Self::resolve_with(&|name| env::var(name).ok(), CVM_ROLE_METADATA_URL).await
+    }
+
+    async fn resolve_with(
+        env: &dyn Fn(&str) -> Option<String>,
+        metadata_url: &str,
+    ) -> Result<Self> {
+        let mut attempts = Vec::new();
+        for prefix in ["TENCENTCLOUD", "TC"] {
+            match Self::from_env_prefix_with(env, prefix) {
+                Ok(credential) => return Ok(credential),
+                Err(e) => attempts.push(format!("environment ({}_*): {}", prefix, e)),
+            }
+        }
+        match Self::from_default_profile_with(env) {
+            Ok(credential) => return Ok(credential),
+            Err(e) => attempts.push(format!("credentials file: {}", e)),
+        }
+        match Self::from_cvm_role_at(metadata_url).await {
+            Ok(credential) => return Ok(credential),
+            Err(e) => attempts.push(format!("CVM role: {}", e)),
+        }
+        Err(TencentCloudError::auth(format!(
+            "No credentials found; tried {}",
+            attempts.join("; ")
+        )))
+    }
+
+    /// Get a copy of the credential with surrounding whitespace removed
+    pub fn trimmed(&self) -> Self {
+        Self {
+            secret_id: self.secret_id.trim().to_string(),
+            secret_key: self.secret_key.trim().to_string(),
+            token: self.token.as_deref().map(|t| t.trim().to_string()),
+        }
     }
 
     /// Validate that the credential has required fields
@@ -91,6 +317,18 @@ impl Credential {
         if self.secret_key.is_empty() {
             return Err(TencentCloudError::auth("Secret Key cannot be empty"));
         }
+        for (name, value) in [
+            ("Secret ID", Some(self.secret_id.as_str())),
+            ("Secret Key", Some(self.secret_key.as_str())),
+            ("Session token", self.token.as_deref()),
+        ] {
+            if value.is_some_and(|value| value.trim() != value) {
+                log::warn!(
+                    "{} has leading or trailing whitespace and will fail signing; use Credential::trimmed",
+                    name
+                );
+            }
+        }
         Ok(())
     }
 
@@ -116,23 +354,17 @@ impl Credential {
 
     /// Update the session token
     pub fn set_token<S: Into<String>>(&mut self, token: Option<S>) {
-        self.token = token.map(|t| t.into());
-    }
-}
-
-impl Default for Credential {
-    fn default() -> Self {
-        Self {
-            secret_id: String::new(),
-            secret_key: String::new(),
-            token: None,
-        }
+        self.token = token.map(|t| t.into().trim().to_string());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    /// Metadata URL that refuses connections, standing in for a non-CVM host
+    const NO_METADATA: &str = "http://127.0.0.1:1";
 
     #[test]
     fn test_credential_new() {
@@ -167,4 +399,69 @@ mod tests {
         assert_eq!(credential.token(), Some("new_token"));
         assert!(credential.has_token());
     }
+
+    #[test]
+    fn test_credential_whitespace_is_trimmed() {
+        let mut credential = Credential::new("test_id\n", " test_key\r\n", Some("test_token\n"));
+        assert_eq!(credential.secret_id(), "test_id");
+        assert_eq!(credential.secret_key(), "test_key");
+        assert_eq!(credential.token(), Some("test_token"));
+
+        credential.set_token(Some("new_token\n"));
+        assert_eq!(credential.token(), Some("new_token"));
+
+        let pasted = Credential {
+            secret_id: "test_id\n".to_string(),
+            secret_key: "test_key".to_string(),
+            token: None,
+        };
+        assert!(pasted.validate().is_ok());
+        assert_eq!(pasted.trimmed().secret_id, "test_id");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_falls_back_to_profile_file() {
+        let path =
+            env::temp_dir().join(format!("tencentcloud-credentials-{}", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "[default]\nsecret_id = default_id\nsecret_key = default_key\n\n\
+             # staging\n[staging]\nsecret_id = staging_id\nsecret_key = staging_key\ntoken = staging_token\n",
+        )
+        .unwrap();
+        let vars = HashMap::from([
+            (CREDENTIALS_FILE_ENV, path.display().to_string()),
+            (PROFILE_ENV, "staging".to_string()),
+            ("TC_SECRET_ID", "partial_id".to_string()),
+        ]);
+        let env = |name: &str| vars.get(name).cloned();
+
+        let credential = Credential::resolve_with(&env, NO_METADATA).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(credential.secret_id(), "staging_id");
+        assert_eq!(credential.secret_key(), "staging_key");
+        assert_eq!(credential.token(), Some("staging_token"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_reports_every_attempt() {
+        let env = |name: &str| match name {
+            "HOME" => Some("/nonexistent-home".to_string()),
+            _ => None,
+        };
+
+        let error = Credential::resolve_with(&env, NO_METADATA)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, TencentCloudError::Auth(_)));
+        let message = error.to_string();
+        for source in [
+            "TENCENTCLOUD_SECRET_ID",
+            "TC_SECRET_ID",
+            "/nonexistent-home/.tencentcloud/credentials",
+            "CVM role",
+        ] {
+            assert!(message.contains(source), "{}", message);
+        }
+    }
 }
@@ -0,0 +1,14 @@
+//! Per-request endpoint selection
+
+use std::fmt::Debug;
+
+/// Chooses the API endpoint for each request
+///
+/// Set on [`ClientProfile::set_endpoint_resolver`](crate::ClientProfile::set_endpoint_resolver)
+/// for multi-CDN or blue/green routing. The returned endpoint takes the same
+/// forms as [`HttpProfile::set_endpoint`](crate::HttpProfile::set_endpoint),
+/// and is used for both the request URL and the signed `Host` header.
+pub trait EndpointResolver: Debug + Send + Sync {
+    /// Get the endpoint for `action` in `region`
+    fn resolve(&self, region: &str, action: &str) -> String;
+}
@@ -0,0 +1,43 @@
+//! Process-wide default client
+
+use crate::core::Client;
+use crate::error::{Result, TencentCloudError};
+use crate::sms::{SendSmsRequest, SendSmsResponse};
+use std::sync::OnceLock;
+
+static DEFAULT_CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Register the process-wide default client
+///
+/// The default client can only be set once; later calls return a
+/// configuration error and leave the registered client in place.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tencentcloud_sms_sdk::{init_default_client, Client, Credential};
+///
+/// let credential = Credential::new("your_secret_id", "your_secret_key", None);
+/// init_default_client(Client::new(credential, "ap-guangzhou")).unwrap();
+/// ```
+pub fn init_default_client(client: Client) -> Result<()> {
+    DEFAULT_CLIENT
+        .set(client)
+        .map_err(|_| TencentCloudError::config("The default client is already initialized"))
+}
+
+/// Get the process-wide default client, if one has been registered
+pub fn default_client() -> Option<&'static Client> {
+    DEFAULT_CLIENT.get()
+}
+
+/// Send SMS through the process-wide default client
+///
+/// Returns a configuration error, without sending anything, if
+/// [`init_default_client`] has not been called.
+pub async fn send_sms(request: SendSmsRequest) -> Result<SendSmsResponse> {
+    let client = default_client().ok_or_else(|| {
+        TencentCloudError::config("The default client is not initialized; call init_default_client")
+    })?;
+    client.send_sms(request).await
+}
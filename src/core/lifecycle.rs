@@ -0,0 +1,60 @@
+//! Tracking of in-flight requests for graceful shutdown
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Count of running requests, closed once shutdown starts
+#[derive(Debug, Default)]
+pub(crate) struct InFlight {
+    count: AtomicUsize,
+    closed: AtomicBool,
+    idle: Notify,
+}
+
+impl InFlight {
+    /// Register a request, or return `None` once shutdown has started
+    pub(crate) fn enter(self: &Arc<Self>) -> Option<InFlightGuard> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        if self.closed.load(Ordering::SeqCst) {
+            self.leave();
+            return None;
+        }
+        Some(InFlightGuard(self.clone()))
+    }
+
+    /// Refuse new requests and wait up to `timeout` for running ones to finish
+    ///
+    /// Returns the number of requests still running when the timeout elapsed.
+    pub(crate) async fn drain(&self, timeout: Duration) -> usize {
+        self.closed.store(true, Ordering::SeqCst);
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register for the wake-up before checking, so none is missed
+            let idle = self.idle.notified();
+            let running = self.count.load(Ordering::SeqCst);
+            if running == 0 {
+                return 0;
+            }
+            if tokio::time::timeout_at(deadline, idle).await.is_err() {
+                return self.count.load(Ordering::SeqCst);
+            }
+        }
+    }
+
+    fn leave(&self) {
+        if self.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Marks a request as running until dropped, including on cancellation
+pub(crate) struct InFlightGuard(Arc<InFlight>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.leave();
+    }
+}
@@ -1,9 +1,30 @@
 //! Core components for the TencentCloud SDK
 
 pub mod client;
+pub mod clock;
 pub mod credential;
+pub mod endpoint;
+pub mod global;
+mod lifecycle;
+pub mod pool;
 pub mod profile;
+pub mod quota;
+pub mod response;
+pub mod retry;
+pub mod signature;
+mod telemetry;
+pub mod transport;
 
 pub use client::Client;
+pub use clock::{Clock, FixedClock, SystemClock};
 pub use credential::Credential;
-pub use profile::{ClientProfile, HttpProfile};
+pub use endpoint::EndpointResolver;
+pub use global::{default_client, init_default_client, send_sms};
+pub use pool::ClientPool;
+pub use profile::{ClientProfile, HttpProfile, Scheme};
+pub use quota::{QuotaBehavior, QuotaTracker};
+pub use response::{RateLimitInfo, RawResponse, RequestId};
+pub use retry::{
+    BackoffStrategy, ConstantBackoff, DecorrelatedJitter, ExponentialBackoff, RetryProfile,
+};
+pub use transport::ConnectionPoolStats;
@@ -0,0 +1,108 @@
+//! Shared transport for multiple clients
+
+use crate::core::transport::Transport;
+use crate::core::{Client, ClientProfile, Credential};
+use std::sync::Arc;
+
+/// A pool of clients sharing one HTTP transport
+///
+/// Every [`Client`] normally builds its own HTTP client with its own
+/// connection pool. Services talking to several regions or SMS apps can use a
+/// `ClientPool` instead, which owns a single transport and vends lightweight
+/// region-specific clients that all reuse it.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::{ClientPool, ClientProfile, Credential};
+///
+/// let credential = Credential::new("your_secret_id", "your_secret_key", None);
+/// let pool = ClientPool::new(credential, ClientProfile::new());
+///
+/// let guangzhou = pool.client("ap-guangzhou");
+/// let beijing = pool.client("ap-beijing");
+/// assert!(guangzhou.shares_transport_with(&beijing));
+/// ```
+pub struct ClientPool {
+    /// Default credentials for vended clients
+    credential: Credential,
+    /// Profile for vended clients
+    profile: ClientProfile,
+    /// Shared HTTP transport
+    transport: Arc<Transport>,
+}
+
+impl ClientPool {
+    /// Create a new pool, building the shared transport from the profile's HTTP settings
+    pub fn new(credential: Credential, profile: ClientProfile) -> Self {
+        Self {
+            credential,
+            profile: profile.clone(),
+            transport: Arc::new(Transport::new(&profile)),
+        }
+    }
+
+    /// Create a new pool around an existing HTTP client
+    pub fn with_http_client(
+        credential: Credential,
+        profile: ClientProfile,
+        http_client: reqwest::Client,
+    ) -> Self {
+        Self {
+            credential,
+            profile,
+            transport: Arc::new(Transport::external(http_client)),
+        }
+    }
+
+    /// Get a client for a region using the pool's credentials
+    pub fn client<S: Into<String>>(&self, region: S) -> Client {
+        self.client_with_credential(self.credential.clone(), region)
+    }
+
+    /// Get a client for a region using different credentials, e.g. for another tenant
+    pub fn client_with_credential<S: Into<String>>(
+        &self,
+        credential: Credential,
+        region: S,
+    ) -> Client {
+        Client::with_shared_transport(
+            credential,
+            region,
+            self.profile.clone(),
+            self.transport.clone(),
+        )
+    }
+
+    /// Get the profile used for vended clients
+    pub fn profile(&self) -> &ClientProfile {
+        &self.profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pooled_clients_share_transport() {
+        let credential = Credential::new("test_id", "test_key", None);
+        let pool = ClientPool::new(credential, ClientProfile::new());
+
+        let guangzhou = pool.client("ap-guangzhou");
+        let beijing = pool.client("ap-beijing");
+        let tenant = pool.client_with_credential(
+            Credential::new("tenant_id", "tenant_key", None),
+            "ap-shanghai",
+        );
+
+        assert_eq!(guangzhou.region(), "ap-guangzhou");
+        assert_eq!(beijing.region(), "ap-beijing");
+        assert_eq!(tenant.region(), "ap-shanghai");
+        assert!(guangzhou.shares_transport_with(&beijing));
+        assert!(beijing.shares_transport_with(&tenant));
+
+        let standalone = Client::new(Credential::new("test_id", "test_key", None), "ap-guangzhou");
+        assert!(!standalone.shares_transport_with(&guangzhou));
+    }
+}
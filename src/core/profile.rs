@@ -1,6 +1,58 @@
 //! Configuration profiles for HTTP and client settings
 
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::endpoint::EndpointResolver;
+use crate::core::quota::{QuotaBehavior, QuotaTracker};
+use crate::core::retry::RetryProfile;
+use crate::core::signature::{ScopeFormat, TC3_ALGORITHM};
+use crate::error::{Result, TencentCloudError};
+use crate::sms::{CategoryPolicy, TemplateRegistry};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Default SMS API version sent in `X-TC-Version`
+pub const DEFAULT_API_VERSION: &str = "2021-01-11";
+
+/// Default SMS API endpoint
+pub const DEFAULT_ENDPOINT: &str = "sms.tencentcloudapi.com";
+
+/// Headers set by the client itself, which extra headers may not replace
+const RESERVED_HEADERS: &[&str] = &[
+    "accept",
+    "authorization",
+    "content-type",
+    "host",
+    "x-tc-action",
+    "x-tc-content-sha256",
+    "x-tc-language",
+    "x-tc-region",
+    "x-tc-timestamp",
+    "x-tc-token",
+    "x-tc-version",
+];
+
+/// URL scheme used for endpoints configured as a bare host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scheme {
+    /// Plain HTTP, mainly for local mocks and test servers
+    Http,
+    /// HTTPS (default)
+    #[default]
+    Https,
+}
+
+impl Scheme {
+    /// Get the scheme as a URL prefix component
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+        }
+    }
+}
 
 /// HTTP configuration profile
 #[derive(Debug, Clone)]
@@ -9,6 +61,8 @@ pub struct HttpProfile {
     pub req_method: String,
     /// API endpoint URL
     pub endpoint: String,
+    /// Scheme used when the endpoint is a bare host
+    pub scheme: Scheme,
     /// Request timeout in seconds
     pub req_timeout: u64,
     /// Connection timeout in seconds
@@ -19,6 +73,8 @@ pub struct HttpProfile {
     pub proxy_host: Option<String>,
     /// Proxy port (optional)
     pub proxy_port: Option<u16>,
+    /// Whether to use proxies from the environment when no proxy is configured
+    pub env_proxy: bool,
     /// User-Agent header
     pub user_agent: String,
 }
@@ -28,12 +84,14 @@ impl HttpProfile {
     pub fn new() -> Self {
         Self {
             req_method: "POST".to_string(),
-            endpoint: "sms.tencentcloudapi.com".to_string(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            scheme: Scheme::Https,
             req_timeout: 60,
             connect_timeout: 60,
             keep_alive: false,
             proxy_host: None,
             proxy_port: None,
+            env_proxy: true,
             user_agent: "TencentCloud-SDK-Rust/1.0.0".to_string(),
         }
     }
@@ -50,6 +108,14 @@ impl HttpProfile {
         self
     }
 
+    /// Set the scheme used when the endpoint is a bare host
+    ///
+    /// Endpoints that already include `http://` or `https://` keep their own scheme.
+    pub fn set_scheme(&mut self, scheme: Scheme) -> &mut Self {
+        self.scheme = scheme;
+        self
+    }
+
     /// Set the request timeout in seconds
     pub fn set_req_timeout(&mut self, timeout: u64) -> &mut Self {
         self.req_timeout = timeout;
@@ -63,6 +129,9 @@ impl HttpProfile {
     }
 
     /// Set the keep-alive setting
+    ///
+    /// Enables TCP keep-alive probes on pooled connections. Connections are
+    /// reused across requests either way; see [`Client`](crate::Client).
     pub fn set_keep_alive(&mut self, keep_alive: bool) -> &mut Self {
         self.keep_alive = keep_alive;
         self
@@ -80,6 +149,17 @@ impl HttpProfile {
         self
     }
 
+    /// Set whether to use proxies from the environment
+    ///
+    /// When enabled (the default), `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and
+    /// `NO_PROXY` are honored as they are by reqwest. A proxy configured with
+    /// [`HttpProfile::set_proxy_host`] and [`HttpProfile::set_proxy_port`]
+    /// always wins, and the environment is then ignored.
+    pub fn use_env_proxy(&mut self, enabled: bool) -> &mut Self {
+        self.env_proxy = enabled;
+        self
+    }
+
     /// Set the User-Agent header
     pub fn set_user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut Self {
         self.user_agent = user_agent.into();
@@ -88,10 +168,20 @@ impl HttpProfile {
 
     /// Get the full endpoint URL with protocol
     pub fn get_full_endpoint(&self) -> String {
-        if self.endpoint.starts_with("http://") || self.endpoint.starts_with("https://") {
-            self.endpoint.clone()
+        self.full_endpoint_for(&self.endpoint)
+    }
+
+    /// Get the endpoint host without any scheme, as used for the `Host` header
+    pub fn get_host(&self) -> &str {
+        endpoint_host(&self.endpoint)
+    }
+
+    /// Get the full URL of `endpoint`, applying this profile's scheme to a bare host
+    pub(crate) fn full_endpoint_for(&self, endpoint: &str) -> String {
+        if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+            endpoint.to_string()
         } else {
-            format!("https://{}", self.endpoint)
+            format!("{}://{}", self.scheme.as_str(), endpoint)
         }
     }
 
@@ -126,6 +216,15 @@ impl Default for HttpProfile {
     }
 }
 
+/// Get the host of an endpoint without any scheme or trailing slash
+pub(crate) fn endpoint_host(endpoint: &str) -> &str {
+    endpoint
+        .strip_prefix("https://")
+        .or_else(|| endpoint.strip_prefix("http://"))
+        .unwrap_or(endpoint)
+        .trim_end_matches('/')
+}
+
 /// Client configuration profile
 #[derive(Debug, Clone)]
 pub struct ClientProfile {
@@ -133,12 +232,53 @@ pub struct ClientProfile {
     pub http_profile: HttpProfile,
     /// Signature method (default: HmacSHA256)
     pub sign_method: String,
+    /// Algorithm label of request signatures (default: TC3-HMAC-SHA256)
+    pub signature_algorithm: String,
+    /// Credential scope layout of request signatures (default: without region)
+    pub scope_format: ScopeFormat,
     /// API version
     pub api_version: String,
     /// Language for error messages
     pub language: String,
     /// Debug mode
     pub debug: bool,
+    /// Send absent optional SendSms fields as explicit `null`s (default: false)
+    pub explicit_nulls: bool,
+    /// Sign `UNSIGNED-PAYLOAD` instead of the body (default: false)
+    pub(crate) unsigned_payload: bool,
+    /// Retry settings
+    pub retry_profile: RetryProfile,
+    /// Local send quota accounting
+    pub quota: QuotaTracker,
+    /// Maximum random delay before a client's first send (default: none)
+    pub startup_jitter: Option<Duration>,
+    /// Seed for the startup delay, for reproducible delays in tests
+    pub startup_jitter_seed: Option<u64>,
+    /// Request timeouts overriding the HTTP profile's, keyed by action name
+    pub action_timeouts: HashMap<String, Duration>,
+    /// Maximum number of API calls in flight at once (default: unlimited)
+    ///
+    /// Private so it cannot drift from the semaphore built alongside it by
+    /// [`ClientProfile::set_max_concurrency`].
+    max_concurrency: Option<usize>,
+    /// Permits bounding concurrent calls, shared by clones of this profile
+    pub(crate) concurrency: Option<Arc<Semaphore>>,
+    /// Content-Type header, also signed (default: application/json)
+    pub content_type: String,
+    /// Extra headers sent with every request; `X-TC-*` ones are signed
+    pub headers: Vec<(String, String)>,
+    /// Optional Accept header
+    pub accept: Option<String>,
+    /// Policy that may veto categorized sends
+    pub category_policy: Option<Arc<dyn CategoryPolicy>>,
+    /// Templates available to [`Client::send_for_template`](crate::Client::send_for_template)
+    pub template_registry: Option<Arc<TemplateRegistry>>,
+    /// Time source for request timestamps
+    pub clock: Arc<dyn Clock>,
+    /// Offset added to the clock when signing; a clock-skew workaround
+    pub time_offset: chrono::Duration,
+    /// Per-request endpoint selection; `None` uses the HTTP profile endpoint
+    pub endpoint_resolver: Option<Arc<dyn EndpointResolver>>,
 }
 
 impl ClientProfile {
@@ -147,9 +287,28 @@ impl ClientProfile {
         Self {
             http_profile: HttpProfile::new(),
             sign_method: "HmacSHA256".to_string(),
-            api_version: "2021-01-11".to_string(),
+            signature_algorithm: TC3_ALGORITHM.to_string(),
+            scope_format: ScopeFormat::default(),
+            api_version: DEFAULT_API_VERSION.to_string(),
             language: "en-US".to_string(),
             debug: false,
+            explicit_nulls: false,
+            unsigned_payload: false,
+            retry_profile: RetryProfile::new(),
+            quota: QuotaTracker::new(),
+            startup_jitter: None,
+            startup_jitter_seed: None,
+            max_concurrency: None,
+            concurrency: None,
+            content_type: "application/json".to_string(),
+            headers: Vec::new(),
+            action_timeouts: HashMap::new(),
+            accept: None,
+            category_policy: None,
+            template_registry: None,
+            clock: Arc::new(SystemClock),
+            time_offset: chrono::Duration::zero(),
+            endpoint_resolver: None,
         }
     }
 
@@ -157,10 +316,7 @@ impl ClientProfile {
     pub fn with_http_profile(http_profile: HttpProfile) -> Self {
         Self {
             http_profile,
-            sign_method: "HmacSHA256".to_string(),
-            api_version: "2021-01-11".to_string(),
-            language: "en-US".to_string(),
-            debug: false,
+            ..Self::new()
         }
     }
 
@@ -170,12 +326,165 @@ impl ClientProfile {
         self
     }
 
+    /// Set the retry profile
+    pub fn set_retry_profile(&mut self, profile: RetryProfile) -> &mut Self {
+        self.retry_profile = profile;
+        self
+    }
+
+    /// Set the maximum number of phone numbers sent per minute
+    ///
+    /// Consulted by [`Client::send_sms_all`](crate::Client::send_sms_all) before
+    /// each chunk.
+    pub fn set_minute_quota(&mut self, quota: u32) -> &mut Self {
+        self.quota.set_minute_quota(quota);
+        self
+    }
+
+    /// Set the maximum number of phone numbers sent per day
+    pub fn set_day_quota(&mut self, quota: u32) -> &mut Self {
+        self.quota.set_day_quota(quota);
+        self
+    }
+
+    /// Delay the first send of each client by a random time up to `max`
+    ///
+    /// Spreads out the initial load when many instances start at once. Only
+    /// sends made before the delay has passed wait for it; later calls are
+    /// unaffected. Clones of a client share the delay.
+    pub fn set_startup_jitter(&mut self, max: Duration) -> &mut Self {
+        self.startup_jitter = Some(max);
+        self
+    }
+
+    /// Set the seed of the startup delay, making it reproducible
+    pub fn set_startup_jitter_seed(&mut self, seed: u64) -> &mut Self {
+        self.startup_jitter_seed = Some(seed);
+        self
+    }
+
+    /// Set the maximum number of API calls in flight at once
+    ///
+    /// Bounds concurrency independently of the send quotas, so a burst of slow
+    /// calls cannot exhaust connections. Calls over the limit wait for a slot;
+    /// retries of a call keep its slot. The limit is shared by every client
+    /// built from this profile or its clones. Values below 1 are raised to 1.
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) -> &mut Self {
+        let max_concurrency = max_concurrency.max(1);
+        self.max_concurrency = Some(max_concurrency);
+        self.concurrency = Some(Arc::new(Semaphore::new(max_concurrency)));
+        self
+    }
+
+    /// Set whether exceeding the minute quota waits or fails
+    pub fn set_quota_behavior(&mut self, behavior: QuotaBehavior) -> &mut Self {
+        self.quota.set_behavior(behavior);
+        self
+    }
+
+    /// Set the Content-Type header
+    ///
+    /// The value is signed as well, so it must describe the JSON body; see
+    /// [`ClientProfile::validate_headers`].
+    pub fn set_content_type<S: Into<String>>(&mut self, content_type: S) -> &mut Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// Add a header sent with every request
+    ///
+    /// Headers named `X-TC-*` are added to the TC3 signature, as the API
+    /// requires for extension headers such as `X-TC-TraceId`; other headers
+    /// are sent unsigned. Headers the client sets itself cannot be overridden;
+    /// see [`ClientProfile::validate_headers`].
+    pub fn add_header<K: Into<String>, V: Into<String>>(&mut self, name: K, value: V) -> &mut Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Override the request timeout of one action
+    ///
+    /// Calls to other actions keep the HTTP profile's request timeout, so
+    /// slow describe or statistics calls can get more time than OTP sends.
+    pub fn set_action_timeout<S: Into<String>>(
+        &mut self,
+        action: S,
+        timeout: Duration,
+    ) -> &mut Self {
+        self.action_timeouts.insert(action.into(), timeout);
+        self
+    }
+
+    /// Set the Accept header
+    pub fn set_accept<S: Into<String>>(&mut self, accept: S) -> &mut Self {
+        self.accept = Some(accept.into());
+        self
+    }
+
+    /// Set the policy consulted before sending requests with a category
+    pub fn set_category_policy<P: CategoryPolicy + 'static>(&mut self, policy: P) -> &mut Self {
+        self.category_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Set the templates available to [`Client::send_for_template`](crate::Client::send_for_template)
+    pub fn set_template_registry(&mut self, registry: TemplateRegistry) -> &mut Self {
+        self.template_registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// Set a resolver choosing the endpoint of each request
+    pub fn set_endpoint_resolver<R: EndpointResolver + 'static>(
+        &mut self,
+        resolver: R,
+    ) -> &mut Self {
+        self.endpoint_resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Set the time source for request timestamps
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) -> &mut Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Shift the signing timestamp by a fixed offset
+    ///
+    /// This is a workaround for hosts whose clock drifts from the API servers
+    /// by more than the signature tolerance and cannot be fixed with NTP right
+    /// away. A positive offset moves the `X-TC-Timestamp` forward, a negative
+    /// one moves it back. Fix the host clock and remove the offset once possible.
+    pub fn set_time_offset(&mut self, offset: chrono::Duration) -> &mut Self {
+        self.time_offset = offset;
+        self
+    }
+
     /// Set the signature method
     pub fn set_sign_method<S: Into<String>>(&mut self, method: S) -> &mut Self {
         self.sign_method = method.into();
         self
     }
 
+    /// Set the algorithm label of request signatures
+    ///
+    /// For testing only: the API accepts nothing but the default
+    /// `TC3-HMAC-SHA256`, so any other label makes every request fail
+    /// authentication. Useful for exercising algorithm-mismatch error handling.
+    pub fn set_signature_algorithm<S: Into<String>>(&mut self, algorithm: S) -> &mut Self {
+        self.signature_algorithm = algorithm.into();
+        self
+    }
+
+    /// Set the credential scope layout of request signatures
+    ///
+    /// SMS expects `{date}/sms/tc3_request`, the default. Use
+    /// [`ScopeFormat::RegionService`] only for an endpoint that requires the
+    /// region in the scope.
+    pub fn set_scope_format(&mut self, format: ScopeFormat) -> &mut Self {
+        self.scope_format = format;
+        self
+    }
+
     /// Set the API version
     pub fn set_api_version<S: Into<String>>(&mut self, version: S) -> &mut Self {
         self.api_version = version.into();
@@ -188,22 +497,219 @@ impl ClientProfile {
         self
     }
 
+    /// Set the language from the system locale
+    ///
+    /// Reads `LC_ALL`, falling back to `LANG`. Locales starting with `zh` map to
+    /// `zh-CN`; any other or unset locale maps to `en-US`.
+    pub fn use_system_language(&mut self) -> &mut Self {
+        self.use_system_language_with(&|name| env::var(name).ok())
+    }
+
+    fn use_system_language_with(&mut self, env: &dyn Fn(&str) -> Option<String>) -> &mut Self {
+        let locale = env("LC_ALL")
+            .filter(|value| !value.is_empty())
+            .or_else(|| env("LANG"))
+            .unwrap_or_default();
+        self.language = language_for_locale(&locale).to_string();
+        self
+    }
+
     /// Set the debug mode
     pub fn set_debug(&mut self, debug: bool) -> &mut Self {
         self.debug = debug;
         self
     }
 
+    /// Set whether absent optional SendSms fields are sent as `null`
+    ///
+    /// By default fields such as `ExtendCode` are left out of the body when
+    /// unset. Enabling this sends them as explicit `null`s instead, to help
+    /// debug schema issues. The signature always covers the body as sent.
+    pub fn set_explicit_nulls(&mut self, explicit_nulls: bool) -> &mut Self {
+        self.explicit_nulls = explicit_nulls;
+        self
+    }
+
+    /// Set whether the request body is left out of the signature
+    ///
+    /// TC3-HMAC-SHA256 normally signs the SHA-256 of the body. With this
+    /// enabled the literal `UNSIGNED-PAYLOAD` is hashed instead and sent in
+    /// the `X-TC-Content-SHA256` header, as the official SDKs do. If a
+    /// signature mismatch goes away with this enabled, the body is being
+    /// changed between signing and sending. The body is then unprotected, so
+    /// only enable this while debugging.
+    #[cfg(feature = "debug-tools")]
+    pub fn set_unsigned_payload(&mut self, unsigned_payload: bool) -> &mut Self {
+        self.unsigned_payload = unsigned_payload;
+        self
+    }
+
     /// Get the HTTP profile
     pub fn get_http_profile(&self) -> &HttpProfile {
         &self.http_profile
     }
 
+    /// Get the retry profile
+    pub fn get_retry_profile(&self) -> &RetryProfile {
+        &self.retry_profile
+    }
+
+    /// Get the send quota tracker
+    pub fn get_quota(&self) -> &QuotaTracker {
+        &self.quota
+    }
+
+    /// Get the maximum startup delay
+    pub fn get_startup_jitter(&self) -> Option<Duration> {
+        self.startup_jitter
+    }
+
+    /// Get the maximum number of API calls in flight at once
+    pub fn get_max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
+
+    /// Get the Content-Type header
+    pub fn get_content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// Get the extra headers sent with every request
+    pub fn get_headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Get the request timeout of an action
+    ///
+    /// Falls back to the HTTP profile's request timeout when the action has
+    /// no override.
+    pub fn get_action_timeout(&self, action: &str) -> Duration {
+        self.action_timeouts
+            .get(action)
+            .copied()
+            .unwrap_or_else(|| self.http_profile.get_req_timeout())
+    }
+
+    /// Get the Accept header
+    pub fn get_accept(&self) -> Option<&str> {
+        self.accept.as_deref()
+    }
+
+    /// Check that the configured headers can be sent and signed consistently
+    ///
+    /// The request body is always JSON and the Content-Type is part of the
+    /// signature, so the media type must be `application/json` or end in
+    /// `+json`, optionally followed by parameters such as `charset=utf-8`.
+    /// Header values must not contain control characters, which would break
+    /// the canonical signing lines. Extra headers must have a plain name and
+    /// must not replace a header set by the client.
+    pub fn validate_headers(&self) -> Result<()> {
+        let media_type = self
+            .content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if media_type != "application/json" && !media_type.ends_with("+json") {
+            return Err(TencentCloudError::config(format!(
+                "Content-Type {:?} does not describe the JSON request body",
+                self.content_type
+            )));
+        }
+
+        for (name, value) in [
+            ("Content-Type", Some(self.content_type.as_str())),
+            ("Accept", self.accept.as_deref()),
+        ]
+        .into_iter()
+        .chain(
+            self.headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), Some(value.as_str()))),
+        ) {
+            if value.is_some_and(|value| value.chars().any(char::is_control)) {
+                return Err(TencentCloudError::config(format!(
+                    "{} header contains control characters",
+                    name
+                )));
+            }
+        }
+
+        for (name, _) in &self.headers {
+            if name.is_empty()
+                || !name
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+            {
+                return Err(TencentCloudError::config(format!(
+                    "Invalid header name {:?}",
+                    name
+                )));
+            }
+            if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                return Err(TencentCloudError::config(format!(
+                    "{} header is set by the client and cannot be added",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the category policy
+    pub fn get_category_policy(&self) -> Option<&dyn CategoryPolicy> {
+        self.category_policy.as_deref()
+    }
+
+    /// Get the template registry
+    pub fn get_template_registry(&self) -> Option<&TemplateRegistry> {
+        self.template_registry.as_deref()
+    }
+
+    /// Get the endpoint resolver, if any
+    pub fn get_endpoint_resolver(&self) -> Option<&dyn EndpointResolver> {
+        self.endpoint_resolver.as_deref()
+    }
+
+    /// Get the endpoint for `action` in `region`, consulting the resolver if set
+    pub fn resolve_endpoint(&self, region: &str, action: &str) -> String {
+        match &self.endpoint_resolver {
+            Some(resolver) => resolver.resolve(region, action),
+            None => self.http_profile.endpoint.clone(),
+        }
+    }
+
+    /// Get the time source for request timestamps
+    pub fn get_clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Get the offset applied to signing timestamps
+    pub fn get_time_offset(&self) -> chrono::Duration {
+        self.time_offset
+    }
+
+    /// Get the time to sign a request with: the clock plus the time offset
+    pub(crate) fn signing_time(&self) -> chrono::DateTime<chrono::Utc> {
+        self.clock.now() + self.time_offset
+    }
+
     /// Get the signature method
     pub fn get_sign_method(&self) -> &str {
         &self.sign_method
     }
 
+    /// Get the algorithm label of request signatures
+    pub fn get_signature_algorithm(&self) -> &str {
+        &self.signature_algorithm
+    }
+
+    /// Get the credential scope layout of request signatures
+    pub fn get_scope_format(&self) -> ScopeFormat {
+        self.scope_format
+    }
+
     /// Get the API version
     pub fn get_api_version(&self) -> &str {
         &self.api_version
@@ -214,6 +720,16 @@ impl ClientProfile {
         &self.language
     }
 
+    /// Check if absent optional SendSms fields are sent as `null`
+    pub fn is_explicit_nulls(&self) -> bool {
+        self.explicit_nulls
+    }
+
+    /// Check if the request body is left out of the signature
+    pub fn is_unsigned_payload(&self) -> bool {
+        self.unsigned_payload
+    }
+
     /// Check if debug mode is enabled
     pub fn is_debug(&self) -> bool {
         self.debug
@@ -226,6 +742,15 @@ impl Default for ClientProfile {
     }
 }
 
+/// Map a POSIX locale such as `zh_CN.UTF-8` to a supported API language
+fn language_for_locale(locale: &str) -> &'static str {
+    if locale.to_ascii_lowercase().starts_with("zh") {
+        "zh-CN"
+    } else {
+        "en-US"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,6 +803,23 @@ mod tests {
         assert_eq!(profile.get_full_endpoint(), "https://custom.endpoint.com");
     }
 
+    #[test]
+    fn test_http_profile_scheme() {
+        let mut profile = HttpProfile::new();
+        assert_eq!(profile.scheme, Scheme::Https);
+
+        profile
+            .set_endpoint("127.0.0.1:8080")
+            .set_scheme(Scheme::Http);
+        assert_eq!(profile.get_full_endpoint(), "http://127.0.0.1:8080");
+        assert_eq!(profile.get_host(), "127.0.0.1:8080");
+
+        // Explicit schemes stay authoritative
+        profile.set_endpoint("https://custom.endpoint.com");
+        assert_eq!(profile.get_full_endpoint(), "https://custom.endpoint.com");
+        assert_eq!(profile.get_host(), "custom.endpoint.com");
+    }
+
     #[test]
     fn test_http_profile_proxy() {
         let mut profile = HttpProfile::new();
@@ -304,6 +846,16 @@ mod tests {
         assert!(!profile.debug);
     }
 
+    #[test]
+    fn test_defaults_match_constants() {
+        assert_eq!(HttpProfile::new().endpoint, DEFAULT_ENDPOINT);
+        assert_eq!(ClientProfile::new().api_version, DEFAULT_API_VERSION);
+        assert_eq!(
+            ClientProfile::with_http_profile(HttpProfile::new()).api_version,
+            DEFAULT_API_VERSION
+        );
+    }
+
     #[test]
     fn test_client_profile_configuration() {
         let mut profile = ClientProfile::new();
@@ -318,4 +870,75 @@ mod tests {
         assert_eq!(profile.language, "zh-CN");
         assert!(profile.debug);
     }
+
+    #[test]
+    fn test_language_for_locale() {
+        assert_eq!(language_for_locale("zh_CN.UTF-8"), "zh-CN");
+        assert_eq!(language_for_locale("zh_TW"), "zh-CN");
+        assert_eq!(language_for_locale("en_US.UTF-8"), "en-US");
+        assert_eq!(language_for_locale("C"), "en-US");
+        assert_eq!(language_for_locale(""), "en-US");
+    }
+
+    #[test]
+    fn test_use_system_language() {
+        let env = |lc_all: &'static str, lang: &'static str| {
+            move |name: &str| match name {
+                "LC_ALL" => Some(lc_all.to_string()),
+                "LANG" => Some(lang.to_string()),
+                _ => None,
+            }
+        };
+        let mut profile = ClientProfile::new();
+        profile.use_system_language_with(&env("zh_CN.UTF-8", "de_DE.UTF-8"));
+        assert_eq!(profile.language, "zh-CN");
+
+        profile.use_system_language_with(&env("de_DE.UTF-8", "zh_CN.UTF-8"));
+        assert_eq!(profile.language, "en-US");
+
+        // An empty LC_ALL falls back to LANG
+        profile.use_system_language_with(&env("", "zh_TW.UTF-8"));
+        assert_eq!(profile.language, "zh-CN");
+
+        profile.use_system_language_with(&|_| None);
+        assert_eq!(profile.language, "en-US");
+    }
+
+    #[test]
+    fn test_validate_headers() {
+        let mut profile = ClientProfile::new();
+        assert!(profile.validate_headers().is_ok());
+
+        profile.set_content_type("application/vnd.api+json");
+        assert!(profile.validate_headers().is_ok());
+
+        profile.set_content_type("text/plain");
+        assert!(profile.validate_headers().is_err());
+
+        profile
+            .set_content_type("application/json")
+            .set_accept("application/json\r\nX-Injected: 1");
+        assert!(profile.validate_headers().is_err());
+    }
+
+    #[test]
+    fn test_validate_extra_headers() {
+        let mut profile = ClientProfile::new();
+        profile
+            .add_header("X-TC-TraceId", "trace-1")
+            .add_header("X-Custom", "value");
+        assert!(profile.validate_headers().is_ok());
+
+        for (name, value) in [
+            ("X-TC-Action", "SendSms"),
+            ("X-TC-Content-SHA256", "UNSIGNED-PAYLOAD"),
+            ("authorization", "x"),
+            ("Bad Name", "x"),
+            ("X-Custom", "a\r\nb"),
+        ] {
+            let mut profile = ClientProfile::new();
+            profile.add_header(name, value);
+            assert!(profile.validate_headers().is_err(), "{}", name);
+        }
+    }
 }
@@ -0,0 +1,202 @@
+//! Local accounting of per-minute and per-day send quotas
+
+use crate::error::{Result, TencentCloudError};
+use std::sync::{Arc, Mutex};
+use tokio::time::{Duration, Instant};
+
+const MINUTE: Duration = Duration::from_secs(60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What to do when a send would exceed the per-minute quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuotaBehavior {
+    /// Wait until the minute window resets
+    #[default]
+    Wait,
+    /// Fail with a quota error
+    Error,
+}
+
+/// Counters for the current quota windows
+#[derive(Debug)]
+struct QuotaWindows {
+    minute_start: Instant,
+    minute_count: u32,
+    day_start: Instant,
+    day_count: u32,
+}
+
+impl QuotaWindows {
+    fn new(now: Instant) -> Self {
+        Self {
+            minute_start: now,
+            minute_count: 0,
+            day_start: now,
+            day_count: 0,
+        }
+    }
+
+    fn reset_expired(&mut self, now: Instant) {
+        if now.duration_since(self.minute_start) >= MINUTE {
+            self.minute_start = now;
+            self.minute_count = 0;
+        }
+        if now.duration_since(self.day_start) >= DAY {
+            self.day_start = now;
+            self.day_count = 0;
+        }
+    }
+}
+
+/// Local tracker of per-minute and per-day send quotas
+///
+/// Counts phone numbers sent in fixed windows that start with the first send
+/// after the previous window expired. The counters are shared between clones,
+/// so every client built from the same profile draws on the same quota. The
+/// accounting is local to this process and does not see sends made elsewhere.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaTracker {
+    minute_quota: Option<u32>,
+    day_quota: Option<u32>,
+    behavior: QuotaBehavior,
+    windows: Arc<Mutex<Option<QuotaWindows>>>,
+}
+
+impl QuotaTracker {
+    /// Create a tracker without any quota
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of phone numbers sent per minute
+    pub fn set_minute_quota(&mut self, quota: u32) -> &mut Self {
+        self.minute_quota = Some(quota);
+        self
+    }
+
+    /// Set the maximum number of phone numbers sent per day
+    pub fn set_day_quota(&mut self, quota: u32) -> &mut Self {
+        self.day_quota = Some(quota);
+        self
+    }
+
+    /// Set what to do when the per-minute quota would be exceeded
+    pub fn set_behavior(&mut self, behavior: QuotaBehavior) -> &mut Self {
+        self.behavior = behavior;
+        self
+    }
+
+    /// Get the per-minute quota
+    pub fn get_minute_quota(&self) -> Option<u32> {
+        self.minute_quota
+    }
+
+    /// Get the per-day quota
+    pub fn get_day_quota(&self) -> Option<u32> {
+        self.day_quota
+    }
+
+    /// Get the behavior when the per-minute quota would be exceeded
+    pub fn get_behavior(&self) -> QuotaBehavior {
+        self.behavior
+    }
+
+    /// Reserve quota for sending to `count` phone numbers
+    ///
+    /// Waits for the minute window to reset or fails, depending on the
+    /// configured [`QuotaBehavior`]. Exceeding the day quota always fails, as
+    /// is a `count` larger than either quota, since waiting cannot help.
+    pub(crate) async fn acquire(&self, count: u32) -> Result<()> {
+        if self.minute_quota.is_none() && self.day_quota.is_none() {
+            return Ok(());
+        }
+
+        loop {
+            let wait = {
+                let now = Instant::now();
+                let mut guard = self.windows.lock().unwrap();
+                let windows = guard.get_or_insert_with(|| QuotaWindows::new(now));
+                windows.reset_expired(now);
+
+                if let Some(quota) = self.day_quota {
+                    if windows.day_count + count > quota {
+                        return Err(TencentCloudError::quota(format!(
+                            "sending {} more numbers would exceed the day quota of {} ({} sent)",
+                            count, quota, windows.day_count
+                        )));
+                    }
+                }
+
+                match self.minute_quota {
+                    Some(quota) if windows.minute_count + count > quota => {
+                        if count > quota || self.behavior == QuotaBehavior::Error {
+                            return Err(TencentCloudError::quota(format!(
+                                "sending {} more numbers would exceed the minute quota of {} ({} sent)",
+                                count, quota, windows.minute_count
+                            )));
+                        }
+                        MINUTE - now.duration_since(windows.minute_start)
+                    }
+                    _ => {
+                        windows.minute_count += count;
+                        windows.day_count += count;
+                        return Ok(());
+                    }
+                }
+            };
+
+            log::debug!("Minute quota reached, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_minute_quota_waits_for_window() {
+        let mut tracker = QuotaTracker::new();
+        tracker.set_minute_quota(3);
+
+        let start = Instant::now();
+        tracker.acquire(2).await.unwrap();
+        tracker.acquire(1).await.unwrap();
+        assert_eq!(Instant::now(), start);
+
+        tracker.acquire(1).await.unwrap();
+        assert_eq!(Instant::now().duration_since(start), MINUTE);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_minute_quota_error() {
+        let mut tracker = QuotaTracker::new();
+        tracker
+            .set_minute_quota(3)
+            .set_behavior(QuotaBehavior::Error);
+
+        tracker.acquire(3).await.unwrap();
+        let error = tracker.acquire(1).await.unwrap_err();
+        assert!(error.is_quota_error());
+
+        tokio::time::advance(MINUTE).await;
+        tracker.acquire(1).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_day_quota_and_shared_counters() {
+        let mut tracker = QuotaTracker::new();
+        tracker.set_minute_quota(10).set_day_quota(4);
+        let clone = tracker.clone();
+
+        tracker.acquire(3).await.unwrap();
+        assert!(clone.acquire(2).await.unwrap_err().is_quota_error());
+        clone.acquire(1).await.unwrap();
+
+        // A request larger than the minute quota can never be sent
+        let mut tracker = QuotaTracker::new();
+        tracker.set_minute_quota(2);
+        assert!(tracker.acquire(3).await.is_err());
+    }
+}
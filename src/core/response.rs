@@ -0,0 +1,147 @@
+//! Transport-level response metadata
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Header carrying the remaining request quota
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "X-RateLimit-Remaining";
+
+/// Header carrying the quota reset time as a UNIX timestamp in seconds
+pub const RATE_LIMIT_RESET_HEADER: &str = "X-RateLimit-Reset";
+
+/// Throttling hints returned in response headers
+///
+/// TencentCloud does not return these headers on every response, so each
+/// field is `None` when the header is absent or cannot be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Remaining requests in the current window
+    pub remaining: Option<u32>,
+    /// When the current window resets, as a UNIX timestamp in seconds
+    pub reset_at: Option<i64>,
+}
+
+impl RateLimitInfo {
+    /// Parse rate-limit hints from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        fn parse<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        }
+
+        Self {
+            remaining: parse(headers, RATE_LIMIT_REMAINING_HEADER),
+            reset_at: parse(headers, RATE_LIMIT_RESET_HEADER),
+        }
+    }
+
+    /// Check if no rate-limit hints were present
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_none() && self.reset_at.is_none()
+    }
+}
+
+/// Request ID assigned by TencentCloud to an API call
+///
+/// Serialized as a plain string, so the wire format is unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Create a request ID from a string
+    pub fn new<S: Into<String>>(id: S) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the request ID as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume the request ID and return the inner string
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl AsRef<str> for RequestId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for RequestId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for RequestId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// An API response together with transport-level metadata
+#[derive(Debug, Clone)]
+pub struct RawResponse<R> {
+    /// Deserialized response
+    pub response: R,
+    /// Rate-limit hints from the response headers
+    pub rate_limit: RateLimitInfo,
+    /// `X-TC-Timestamp` the request was signed with, in UNIX seconds
+    pub timestamp: i64,
+    /// `RequestId` returned by the API, if any
+    pub request_id: Option<RequestId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(RateLimitInfo::from_headers(&headers).is_empty());
+
+        headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from_static("42"));
+        headers.insert(
+            RATE_LIMIT_RESET_HEADER,
+            HeaderValue::from_static("1700000000"),
+        );
+        let info = RateLimitInfo::from_headers(&headers);
+        assert_eq!(info.remaining, Some(42));
+        assert_eq!(info.reset_at, Some(1700000000));
+    }
+
+    #[test]
+    fn test_rate_limit_malformed_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from_static("-1"));
+        headers.insert(RATE_LIMIT_RESET_HEADER, HeaderValue::from_static("soon"));
+        assert!(RateLimitInfo::from_headers(&headers).is_empty());
+    }
+}
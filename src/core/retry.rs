@@ -0,0 +1,269 @@
+//! Retry configuration and backoff strategies
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Computes how long to wait before a retry
+pub trait BackoffStrategy: Debug + Send + Sync {
+    /// Get the delay before retry number `attempt`, starting at 1
+    fn next_delay(&self, attempt: u32) -> Duration;
+}
+
+/// Exponential backoff: `base * 2^(attempt - 1)`, capped at `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExponentialBackoff {
+    /// Delay before the first retry
+    pub base: Duration,
+    /// Maximum delay
+    pub max: Duration,
+}
+
+impl ExponentialBackoff {
+    /// Create a new exponential backoff
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max }
+    }
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100), Duration::from_secs(10))
+    }
+}
+
+impl BackoffStrategy for ExponentialBackoff {
+    fn next_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Constant backoff: the same delay before every retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstantBackoff {
+    /// Delay before each retry
+    pub delay: Duration,
+}
+
+impl ConstantBackoff {
+    /// Create a new constant backoff
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+}
+
+impl BackoffStrategy for ConstantBackoff {
+    fn next_delay(&self, _attempt: u32) -> Duration {
+        self.delay
+    }
+}
+
+/// Decorrelated jitter: each delay is random between `base` and three times the
+/// previous delay, capped at `max`
+///
+/// Spreads out retries from many clients that failed at the same time. The
+/// random sequence is derived from `seed`, so a fixed seed gives reproducible
+/// delays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecorrelatedJitter {
+    /// Minimum delay
+    pub base: Duration,
+    /// Maximum delay
+    pub max: Duration,
+    /// Seed for the random sequence
+    pub seed: u64,
+}
+
+impl DecorrelatedJitter {
+    /// Create a new decorrelated jitter backoff seeded from the current time
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self::with_seed(base, max, time_seed())
+    }
+
+    /// Create a new decorrelated jitter backoff with a fixed seed
+    pub fn with_seed(base: Duration, max: Duration, seed: u64) -> Self {
+        Self { base, max, seed }
+    }
+}
+
+impl BackoffStrategy for DecorrelatedJitter {
+    fn next_delay(&self, attempt: u32) -> Duration {
+        let base = self.base.as_millis() as u64;
+        let max = self.max.as_millis() as u64;
+        let mut delay = base;
+        for step in 1..=attempt.max(1) {
+            let upper = delay.saturating_mul(3).max(base + 1);
+            let random = splitmix64(self.seed.wrapping_add(u64::from(step)));
+            delay = (base + random % (upper - base)).min(max);
+        }
+        Duration::from_millis(delay)
+    }
+}
+
+/// Seed for the PRNG taken from the current time
+pub(crate) fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// SplitMix64 mixing function, used as a small deterministic PRNG
+pub(crate) fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Retry configuration profile
+///
+/// Retries apply to network errors, timeouts and transient API errors (see
+/// [`TencentCloudError::is_retryable`](crate::error::TencentCloudError::is_retryable)).
+/// Every attempt is signed again with a fresh timestamp. Retries are disabled
+/// by default.
+///
+/// # Timeouts
+///
+/// The HTTP profile's `req_timeout` bounds every attempt. A per-attempt
+/// timeout set here bounds each attempt as well, so the shorter of the two
+/// applies; an attempt cut off by either is a retryable timeout error. The
+/// total timeout bounds the whole call, including backoff delays, and is not
+/// retried.
+#[derive(Debug, Clone)]
+pub struct RetryProfile {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+    /// Strategy computing the delay between attempts
+    pub backoff: Arc<dyn BackoffStrategy>,
+    /// Time limit for each attempt
+    pub per_attempt_timeout: Option<Duration>,
+    /// Time limit for all attempts together
+    pub total_timeout: Option<Duration>,
+}
+
+impl RetryProfile {
+    /// Create a new retry profile with retries disabled
+    pub fn new() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Arc::new(ExponentialBackoff::default()),
+            per_attempt_timeout: None,
+            total_timeout: None,
+        }
+    }
+
+    /// Set the maximum number of retries
+    pub fn set_max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the backoff strategy
+    pub fn set_backoff<B: BackoffStrategy + 'static>(&mut self, backoff: B) -> &mut Self {
+        self.backoff = Arc::new(backoff);
+        self
+    }
+
+    /// Set the time limit for each attempt
+    pub fn set_per_attempt_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.per_attempt_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the time limit for all attempts together, including backoff delays
+    pub fn set_total_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Get the maximum number of retries
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Get the backoff strategy
+    pub fn get_backoff(&self) -> &dyn BackoffStrategy {
+        self.backoff.as_ref()
+    }
+
+    /// Get the time limit for each attempt
+    pub fn get_per_attempt_timeout(&self) -> Option<Duration> {
+        self.per_attempt_timeout
+    }
+
+    /// Get the time limit for all attempts together
+    pub fn get_total_timeout(&self) -> Option<Duration> {
+        self.total_timeout
+    }
+}
+
+impl Default for RetryProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delays(strategy: &dyn BackoffStrategy, attempts: u32) -> Vec<u64> {
+        (1..=attempts)
+            .map(|attempt| strategy.next_delay(attempt).as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let backoff =
+            ExponentialBackoff::new(Duration::from_millis(100), Duration::from_millis(1000));
+        assert_eq!(delays(&backoff, 6), vec![100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(backoff.next_delay(100), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_constant_backoff() {
+        let backoff = ConstantBackoff::new(Duration::from_millis(250));
+        assert_eq!(delays(&backoff, 3), vec![250, 250, 250]);
+    }
+
+    #[test]
+    fn test_decorrelated_jitter() {
+        let backoff =
+            DecorrelatedJitter::with_seed(Duration::from_millis(100), Duration::from_secs(5), 42);
+        let sequence = delays(&backoff, 8);
+
+        // Deterministic for a fixed seed
+        assert_eq!(sequence, delays(&backoff, 8));
+
+        // Each delay stays between base and three times the previous delay, capped at max
+        let mut previous = 100;
+        for delay in sequence {
+            assert!(delay >= 100);
+            assert!(delay <= 5000);
+            assert!(delay < (previous * 3).max(101));
+            previous = delay;
+        }
+
+        let other =
+            DecorrelatedJitter::with_seed(Duration::from_millis(100), Duration::from_secs(5), 7);
+        assert_ne!(delays(&backoff, 8), delays(&other, 8));
+    }
+
+    #[test]
+    fn test_retry_profile() {
+        let mut profile = RetryProfile::new();
+        assert_eq!(profile.get_max_retries(), 0);
+
+        profile
+            .set_max_retries(3)
+            .set_backoff(ConstantBackoff::new(Duration::from_millis(10)));
+        assert_eq!(profile.get_max_retries(), 3);
+        assert_eq!(
+            profile.get_backoff().next_delay(2),
+            Duration::from_millis(10)
+        );
+    }
+}
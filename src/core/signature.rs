@@ -0,0 +1,419 @@
+//! TC3-HMAC-SHA256 signing helpers
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Algorithm label of TencentCloud API 3.0 signatures
+pub const TC3_ALGORITHM: &str = "TC3-HMAC-SHA256";
+
+/// Terminator of every TC3 credential scope
+pub const TC3_REQUEST: &str = "tc3_request";
+
+/// Compute the credential scope of a TC3-HMAC-SHA256 signature
+///
+/// The scope is `{date}/{service}/tc3_request`, where the date is the UTC
+/// date of the request timestamp formatted as `YYYY-MM-DD`. It appears in the
+/// `Credential=` part of the `Authorization` header, so comparing it against
+/// a failing request helps diagnose clock skew and service mismatches.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use tencentcloud_sms_sdk::core::signature::credential_scope;
+///
+/// let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+/// assert_eq!(credential_scope(timestamp, "sms"), "2021-01-01/sms/tc3_request");
+/// ```
+pub fn credential_scope(timestamp: DateTime<Utc>, service: &str) -> String {
+    credential_scope_with(ScopeFormat::default(), timestamp, "", service)
+}
+
+/// Layout of a TC3 credential scope
+///
+/// SMS signs without a region, which is the default. Some TencentCloud
+/// products put the region in the scope as well; select that layout with
+/// [`Signer::set_scope_format`] or
+/// [`ClientProfile::set_scope_format`](crate::ClientProfile::set_scope_format).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ScopeFormat {
+    /// `{date}/{service}/tc3_request`
+    #[default]
+    Service,
+    /// `{date}/{region}/{service}/tc3_request`
+    RegionService,
+}
+
+/// Compute a credential scope in the given layout
+///
+/// `region` is ignored by [`ScopeFormat::Service`].
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use tencentcloud_sms_sdk::core::signature::{credential_scope_with, ScopeFormat};
+///
+/// let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+/// assert_eq!(
+///     credential_scope_with(ScopeFormat::RegionService, timestamp, "ap-guangzhou", "cvm"),
+///     "2021-01-01/ap-guangzhou/cvm/tc3_request"
+/// );
+/// ```
+pub fn credential_scope_with(
+    format: ScopeFormat,
+    timestamp: DateTime<Utc>,
+    region: &str,
+    service: &str,
+) -> String {
+    let date = timestamp.format("%Y-%m-%d");
+    match format {
+        ScopeFormat::Service => format!("{}/{}/{}", date, service, TC3_REQUEST),
+        ScopeFormat::RegionService => {
+            format!("{}/{}/{}/{}", date, region, service, TC3_REQUEST)
+        }
+    }
+}
+
+/// Build the TC3 string to sign
+///
+/// The string is `{algorithm}\n{timestamp}\n{credential_scope}\n{hash}`,
+/// where `hash` is the lowercase hex SHA-256 of the canonical request. The
+/// algorithm label is a parameter so tests can build strings with a label
+/// other than [`TC3_ALGORITHM`], e.g. to exercise algorithm-mismatch errors;
+/// see [`Signer::set_algorithm`] for signing requests with such a label.
+pub fn string_to_sign(
+    algorithm: &str,
+    timestamp: i64,
+    credential_scope: &str,
+    hashed_canonical_request: &str,
+) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        algorithm, timestamp, credential_scope, hashed_canonical_request
+    )
+}
+
+/// Parts of a computed signature, kept for debugging
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignResult {
+    /// Lowercase hex HMAC-SHA256 signature
+    pub signature: String,
+    /// Credential scope the signature was computed for
+    pub credential_scope: String,
+    /// Canonical request that was hashed
+    pub canonical_request: String,
+    /// String the signature was computed over
+    pub string_to_sign: String,
+}
+
+/// TC3-HMAC-SHA256 request signer
+///
+/// The signing key only changes with the UTC date, so the signer derives it
+/// once per date and reuses it for every request signed on that date. Hits and
+/// misses of this cache are counted, see [`Signer::cache_stats`].
+pub struct Signer {
+    secret_id: String,
+    secret_key: String,
+    service: String,
+    algorithm: String,
+    scope_format: ScopeFormat,
+    /// Signing key of the most recent date, as `(date, key)`
+    key_cache: Mutex<Option<(String, Vec<u8>)>>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+}
+
+impl Signer {
+    /// Create a signer for a service
+    pub fn new<I: Into<String>, K: Into<String>, S: Into<String>>(
+        secret_id: I,
+        secret_key: K,
+        service: S,
+    ) -> Self {
+        Self {
+            secret_id: secret_id.into(),
+            secret_key: secret_key.into(),
+            service: service.into(),
+            algorithm: TC3_ALGORITHM.to_string(),
+            scope_format: ScopeFormat::default(),
+            key_cache: Mutex::new(None),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the algorithm label of signatures (default: [`TC3_ALGORITHM`])
+    ///
+    /// Only the label in the string to sign and the `Authorization` header
+    /// changes; the signature is still HMAC-SHA256. The API rejects any
+    /// other label, which is what makes this useful for testing
+    /// algorithm-mismatch error handling.
+    pub fn set_algorithm<S: Into<String>>(&mut self, algorithm: S) -> &mut Self {
+        self.algorithm = algorithm.into();
+        self
+    }
+
+    /// Get the algorithm label of signatures
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    /// Set the credential scope layout (default: [`ScopeFormat::Service`])
+    ///
+    /// The layout changes the scope in the string to sign and the
+    /// `Authorization` header; the signing key is derived from the date and
+    /// service either way.
+    pub fn set_scope_format(&mut self, format: ScopeFormat) -> &mut Self {
+        self.scope_format = format;
+        self
+    }
+
+    /// Get the credential scope layout
+    pub fn scope_format(&self) -> ScopeFormat {
+        self.scope_format
+    }
+
+    /// Sign a request
+    ///
+    /// `canonical_headers` is the newline-terminated `name:value` list of the
+    /// signed headers and `signed_headers` their `;`-separated names. The
+    /// request is signed with URI `/` and an empty query string. `region` only
+    /// enters the signature with [`ScopeFormat::RegionService`].
+    pub fn sign(
+        &self,
+        method: &str,
+        canonical_headers: &str,
+        signed_headers: &str,
+        hashed_payload: &str,
+        timestamp: i64,
+        region: &str,
+    ) -> SignResult {
+        let time = DateTime::from_timestamp(timestamp, 0).unwrap_or_default();
+        let date = time.format("%Y-%m-%d").to_string();
+        let credential_scope =
+            credential_scope_with(self.scope_format, time, region, &self.service);
+        let canonical_request = format!(
+            "{}\n/\n\n{}\n{}\n{}",
+            method, canonical_headers, signed_headers, hashed_payload
+        );
+        let string_to_sign = string_to_sign(
+            &self.algorithm,
+            timestamp,
+            &credential_scope,
+            &sha256_hex(&canonical_request),
+        );
+        let signature = hex::encode(hmac_sha256(
+            &self.signing_key(&date),
+            string_to_sign.as_bytes(),
+        ));
+
+        SignResult {
+            signature,
+            credential_scope,
+            canonical_request,
+            string_to_sign,
+        }
+    }
+
+    /// Build the `Authorization` header value for a signed request
+    pub fn authorization(&self, result: &SignResult, signed_headers: &str) -> String {
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.algorithm,
+            self.secret_id,
+            result.credential_scope,
+            signed_headers,
+            result.signature
+        )
+    }
+
+    /// Derive and cache the signing key for the UTC date of `timestamp`
+    ///
+    /// Signing does this on demand; calling it ahead of time takes the key
+    /// derivation off the first request of the day.
+    pub fn prepare(&self, timestamp: i64) {
+        let time = DateTime::from_timestamp(timestamp, 0).unwrap_or_default();
+        self.signing_key(&time.format("%Y-%m-%d").to_string());
+    }
+
+    /// Get the signing key cache statistics as `(hits, misses)`
+    ///
+    /// Every signature looks the key up once. A steady stream of misses on
+    /// the same date means the cache is thrashing.
+    pub fn cache_stats(&self) -> (u64, u64) {
+        (
+            self.cache_hits.load(Ordering::Relaxed),
+            self.cache_misses.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Get the signing key of a `YYYY-MM-DD` date, deriving it on a cache miss
+    fn signing_key(&self, date: &str) -> Vec<u8> {
+        let mut cache = self
+            .key_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((cached_date, key)) = cache.as_ref() {
+            if cached_date == date {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                return key.clone();
+            }
+        }
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+
+        let secret = format!("TC3{}", self.secret_key);
+        let key = hmac_sha256(secret.as_bytes(), date.as_bytes());
+        let key = hmac_sha256(&key, self.service.as_bytes());
+        let key = hmac_sha256(&key, TC3_REQUEST.as_bytes());
+        *cache = Some((date.to_string(), key.clone()));
+        key
+    }
+}
+
+/// Compute the lowercase hex SHA-256 of a string
+pub(crate) fn sha256_hex(data: &str) -> String {
+    hex::encode(Sha256::digest(data.as_bytes()))
+}
+
+/// Compute the HMAC-SHA256 of `message` under `key`
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_credential_scope() {
+        let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            credential_scope(timestamp, "sms"),
+            "2021-01-01/sms/tc3_request"
+        );
+
+        // The date is taken in UTC, even late in the day
+        let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 23, 59, 59).unwrap();
+        assert_eq!(
+            credential_scope(timestamp, "sms"),
+            "2021-01-01/sms/tc3_request"
+        );
+    }
+
+    #[test]
+    fn test_credential_scope_formats() {
+        let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            credential_scope_with(ScopeFormat::Service, timestamp, "ap-guangzhou", "sms"),
+            "2021-01-01/sms/tc3_request"
+        );
+        assert_eq!(
+            credential_scope_with(ScopeFormat::RegionService, timestamp, "ap-guangzhou", "sms"),
+            "2021-01-01/ap-guangzhou/sms/tc3_request"
+        );
+        assert_eq!(ScopeFormat::default(), ScopeFormat::Service);
+    }
+
+    #[test]
+    fn test_string_to_sign() {
+        let timestamp = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let scope = credential_scope(timestamp, "sms");
+        let hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        let string = string_to_sign(TC3_ALGORITHM, timestamp.timestamp(), &scope, hash);
+        assert!(string.starts_with("TC3-HMAC-SHA256\n1609459200\n"));
+        assert_eq!(string.lines().nth(2), Some("2021-01-01/sms/tc3_request"));
+
+        let string = string_to_sign("TC4-HMAC-SHA512", timestamp.timestamp(), &scope, hash);
+        assert!(string.starts_with("TC4-HMAC-SHA512\n"));
+    }
+
+    #[test]
+    fn test_signer_matches_tc3_key_derivation() {
+        let signer = Signer::new("test_id", "test_key", "sms");
+        let headers = "content-type:application/json\nhost:sms.tencentcloudapi.com\n";
+        let payload = sha256_hex("{}");
+        let result = signer.sign(
+            "POST",
+            headers,
+            "content-type;host",
+            &payload,
+            1609459200,
+            "ap-guangzhou",
+        );
+
+        assert_eq!(result.credential_scope, "2021-01-01/sms/tc3_request");
+        let key = hmac_sha256(b"TC3test_key", b"2021-01-01");
+        let key = hmac_sha256(&key, b"sms");
+        let key = hmac_sha256(&key, b"tc3_request");
+        assert_eq!(
+            result.signature,
+            hex::encode(hmac_sha256(&key, result.string_to_sign.as_bytes()))
+        );
+        assert_eq!(
+            signer.authorization(&result, "content-type;host"),
+            format!(
+                "TC3-HMAC-SHA256 Credential=test_id/2021-01-01/sms/tc3_request, \
+                 SignedHeaders=content-type;host, Signature={}",
+                result.signature
+            )
+        );
+    }
+
+    #[test]
+    fn test_signer_algorithm_label() {
+        let mut signer = Signer::new("test_id", "test_key", "sms");
+        assert_eq!(signer.algorithm(), TC3_ALGORITHM);
+        let result = signer.sign("POST", "", "", "", 1609459200, "ap-guangzhou");
+        assert!(result.string_to_sign.starts_with("TC3-HMAC-SHA256\n"));
+
+        signer.set_algorithm("TC4-HMAC-SHA512");
+        let result = signer.sign("POST", "", "", "", 1609459200, "ap-guangzhou");
+        assert!(result.string_to_sign.starts_with("TC4-HMAC-SHA512\n"));
+        assert!(signer
+            .authorization(&result, "content-type;host")
+            .starts_with("TC4-HMAC-SHA512 Credential=test_id/"));
+    }
+
+    #[test]
+    fn test_signer_scope_format() {
+        let mut signer = Signer::new("test_id", "test_key", "sms");
+        let result = signer.sign("POST", "", "", "", 1609459200, "ap-guangzhou");
+        assert_eq!(result.credential_scope, "2021-01-01/sms/tc3_request");
+        let service_only = result.signature;
+
+        signer.set_scope_format(ScopeFormat::RegionService);
+        let result = signer.sign("POST", "", "", "", 1609459200, "ap-guangzhou");
+        assert_eq!(
+            result.credential_scope,
+            "2021-01-01/ap-guangzhou/sms/tc3_request"
+        );
+        assert_eq!(
+            result.string_to_sign.lines().nth(2),
+            Some(result.credential_scope.as_str())
+        );
+        assert_ne!(result.signature, service_only);
+    }
+
+    #[test]
+    fn test_signing_key_cache_stats() {
+        let signer = Signer::new("test_id", "test_key", "sms");
+        assert_eq!(signer.cache_stats(), (0, 0));
+
+        // Two signatures on the same UTC date derive the key once
+        signer.sign("POST", "", "", "", 1609459200, "ap-guangzhou");
+        signer.sign("POST", "", "", "", 1609459200 + 3600, "ap-guangzhou");
+        assert_eq!(signer.cache_stats(), (1, 1));
+
+        // The next UTC date needs a new key
+        signer.sign("POST", "", "", "", 1609459200 + 86400, "ap-guangzhou");
+        assert_eq!(signer.cache_stats(), (1, 2));
+    }
+}
@@ -0,0 +1,52 @@
+//! OpenTelemetry spans for API calls
+//!
+//! With the `opentelemetry` feature enabled, every API call runs inside a
+//! `tracing` span carrying the OpenTelemetry RPC semantic attributes. Install
+//! the `tracing-opentelemetry` layer in your subscriber to export these spans;
+//! without the feature the helpers here do nothing.
+
+use std::future::Future;
+
+/// Run an API call inside a span describing it
+#[cfg(feature = "opentelemetry")]
+pub(crate) async fn instrument<F: Future>(
+    future: F,
+    service: &str,
+    action: &str,
+    host: &str,
+) -> F::Output {
+    use tracing::Instrument;
+
+    let span = tracing::info_span!(
+        "tencentcloud.request",
+        otel.name = %format!("{}/{}", service, action),
+        otel.kind = "client",
+        rpc.system = "tencentcloud",
+        rpc.service = %service,
+        rpc.method = %action,
+        peer.service = %host,
+        request_id = tracing::field::Empty,
+    );
+    future.instrument(span).await
+}
+
+/// Run an API call inside a span describing it
+#[cfg(not(feature = "opentelemetry"))]
+pub(crate) async fn instrument<F: Future>(
+    future: F,
+    _service: &str,
+    _action: &str,
+    _host: &str,
+) -> F::Output {
+    future.await
+}
+
+/// Record the request ID returned by the API on the current span
+#[cfg(feature = "opentelemetry")]
+pub(crate) fn record_request_id(request_id: &str) {
+    tracing::Span::current().record("request_id", request_id);
+}
+
+/// Record the request ID returned by the API on the current span
+#[cfg(not(feature = "opentelemetry"))]
+pub(crate) fn record_request_id(_request_id: &str) {}
@@ -0,0 +1,212 @@
+//! HTTP transport with connection reuse accounting
+
+use crate::core::{ClientProfile, HttpProfile};
+// reqwest 0.11 takes this type in `Resolve` but does not re-export it
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use url::{Host, Url};
+
+/// Connection reuse statistics of a client's HTTP transport
+///
+/// Returned by [`Client::connection_pool_stats`](crate::Client::connection_pool_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionPoolStats {
+    /// HTTP requests sent, including retries
+    pub requests: u64,
+    /// New connections opened; every other request reused a pooled one
+    pub connections_opened: u64,
+}
+
+impl ConnectionPoolStats {
+    /// Get the number of requests that reused a pooled connection
+    pub fn reused(&self) -> u64 {
+        self.requests.saturating_sub(self.connections_opened)
+    }
+}
+
+/// Counters behind [`ConnectionPoolStats`]
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    connections_opened: AtomicU64,
+    /// Set once a request went to a host the resolver never sees
+    unobserved: AtomicBool,
+}
+
+/// DNS resolver counting lookups, and thereby new connections
+///
+/// reqwest 0.11 offers no hook on its connector, so new connections are
+/// counted where the connector resolves the host name: once per connection,
+/// and never for a request sent over a pooled one. Connections to an IP
+/// address and through a proxy are not resolved this way, see
+/// [`Transport::stats`].
+struct CountingResolver {
+    counters: Arc<Counters>,
+}
+
+impl Resolve for CountingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        self.counters
+            .connections_opened
+            .fetch_add(1, Ordering::Relaxed);
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str().to_string(), 0)).await?;
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
+/// HTTP client used by [`Client`](crate::Client), possibly shared by several
+pub(crate) struct Transport {
+    client: reqwest::Client,
+    /// `None` for caller-supplied HTTP clients and proxied transports, which
+    /// cannot be instrumented
+    counters: Option<Arc<Counters>>,
+}
+
+impl Transport {
+    /// Build a transport from the profile's HTTP settings
+    ///
+    /// The transport is instrumented unless it sends through a proxy.
+    pub(crate) fn new(profile: &ClientProfile) -> Self {
+        let counters = Arc::new(Counters::default());
+        let resolver = Arc::new(CountingResolver {
+            counters: counters.clone(),
+        });
+        let proxied = uses_proxy(profile.get_http_profile(), &|name| env::var(name).ok());
+        Self {
+            client: build_http_client(profile, resolver),
+            counters: (!proxied).then_some(counters),
+        }
+    }
+
+    /// Wrap an HTTP client configured by the caller
+    pub(crate) fn external(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            counters: None,
+        }
+    }
+
+    /// Get the HTTP client
+    pub(crate) fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Count a request about to be sent to `url`
+    pub(crate) fn record_request(&self, url: &str) {
+        if let Some(counters) = &self.counters {
+            counters.requests.fetch_add(1, Ordering::Relaxed);
+            if !is_resolved(url) {
+                counters.unobserved.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Get the connection reuse statistics, if this transport is instrumented
+    ///
+    /// `None` once a request went to an IP address, whose connections the
+    /// resolver cannot count.
+    pub(crate) fn stats(&self) -> Option<ConnectionPoolStats> {
+        self.counters
+            .as_ref()
+            .filter(|counters| !counters.unobserved.load(Ordering::Relaxed))
+            .map(|counters| ConnectionPoolStats {
+                requests: counters.requests.load(Ordering::Relaxed),
+                connections_opened: counters.connections_opened.load(Ordering::Relaxed),
+            })
+    }
+}
+
+/// Check whether the connector resolves the host of a URL by name
+fn is_resolved(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| matches!(url.host(), Some(Host::Domain(_))))
+}
+
+/// Check whether requests go through a proxy, given a way to read the environment
+///
+/// The connector then resolves the proxy host rather than the endpoint.
+fn uses_proxy(http_profile: &HttpProfile, env: &dyn Fn(&str) -> Option<String>) -> bool {
+    if http_profile.get_proxy_url().is_some() {
+        return true;
+    }
+    http_profile.env_proxy
+        && [
+            "HTTP_PROXY",
+            "http_proxy",
+            "HTTPS_PROXY",
+            "https_proxy",
+            "ALL_PROXY",
+            "all_proxy",
+        ]
+        .iter()
+        .any(|name| env(name).is_some_and(|value| !value.is_empty()))
+}
+
+/// Build an HTTP client from the HTTP settings of a client profile
+fn build_http_client(profile: &ClientProfile, resolver: Arc<CountingResolver>) -> reqwest::Client {
+    let http_profile = profile.get_http_profile();
+
+    let mut client_builder = reqwest::Client::builder()
+        .timeout(http_profile.get_req_timeout())
+        .connect_timeout(http_profile.get_connect_timeout())
+        .tcp_keepalive(if http_profile.keep_alive {
+            Some(Duration::from_secs(60))
+        } else {
+            None
+        })
+        .user_agent(&http_profile.user_agent)
+        // Decode gzip bodies, including error envelopes from compressing proxies
+        .gzip(true)
+        .dns_resolver(resolver);
+
+    // Configure proxy if set. An explicit proxy replaces the environment
+    // proxies, which reqwest picks up otherwise.
+    if let Some(proxy_url) = http_profile.get_proxy_url() {
+        if let Ok(proxy) = reqwest::Proxy::all(&proxy_url) {
+            client_builder = client_builder.proxy(proxy);
+        }
+    } else if !http_profile.env_proxy {
+        client_builder = client_builder.no_proxy();
+    }
+
+    client_builder
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_resolved() {
+        assert!(is_resolved("https://sms.tencentcloudapi.com"));
+        assert!(is_resolved("http://localhost:8080/"));
+        assert!(!is_resolved("http://127.0.0.1:8080"));
+        assert!(!is_resolved("http://[::1]:8080"));
+        assert!(!is_resolved("not a url"));
+    }
+
+    #[test]
+    fn test_uses_proxy() {
+        let no_env = |_: &str| None;
+        let env = |name: &str| (name == "https_proxy").then(|| "http://proxy:3128".to_string());
+
+        let mut http_profile = HttpProfile::new();
+        assert!(!uses_proxy(&http_profile, &no_env));
+        assert!(uses_proxy(&http_profile, &env));
+
+        http_profile.use_env_proxy(false);
+        assert!(!uses_proxy(&http_profile, &env));
+
+        http_profile
+            .set_proxy_host(Some("proxy"))
+            .set_proxy_port(Some(3128));
+        assert!(uses_proxy(&http_profile, &no_env));
+    }
+}
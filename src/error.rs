@@ -1,5 +1,6 @@
 //! Error types for the TencentCloud SMS SDK
 
+use crate::core::response::RequestId;
 use thiserror::Error;
 
 /// Result type alias for TencentCloud operations
@@ -24,7 +25,7 @@ pub enum TencentCloudError {
         /// Error message returned by the API
         message: String,
         /// Request ID for debugging
-        request_id: Option<String>,
+        request_id: Option<RequestId>,
     },
 
     /// Authentication errors
@@ -47,6 +48,14 @@ pub enum TencentCloudError {
     #[error("Timeout error: {0}")]
     Timeout(String),
 
+    /// Local send quota exceeded
+    #[error("Quota exceeded: {0}")]
+    Quota(String),
+
+    /// Send vetoed by a local category policy
+    #[error("Policy rejected: {0}")]
+    Policy(String),
+
     /// Generic errors
     #[error("Error: {0}")]
     Other(String),
@@ -71,7 +80,7 @@ impl TencentCloudError {
         Self::Api {
             code: code.into(),
             message: message.into(),
-            request_id: request_id.map(|s| s.into()),
+            request_id: request_id.map(RequestId::new),
         }
     }
 
@@ -100,11 +109,53 @@ impl TencentCloudError {
         Self::Timeout(message.into())
     }
 
+    /// Create a new quota error
+    pub fn quota<S: Into<String>>(message: S) -> Self {
+        Self::Quota(message.into())
+    }
+
+    /// Create a new policy error
+    pub fn policy<S: Into<String>>(message: S) -> Self {
+        Self::Policy(message.into())
+    }
+
     /// Create a new generic error
     pub fn other<S: Into<String>>(message: S) -> Self {
         Self::Other(message.into())
     }
 
+    /// Parse an API error from a raw response body
+    ///
+    /// Returns `None` if the body is not JSON or carries no error. See
+    /// [`TencentCloudError::from_response_value`] for the accepted shapes.
+    pub fn from_response_json(body: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        Self::from_response_value(&value)
+    }
+
+    /// Parse the API error of a decoded response body
+    ///
+    /// Accepts the current `{"Response":{"Error":{...},"RequestId":...}}`
+    /// envelope and, if that is absent, the legacy top-level
+    /// `{"Error":{...},"RequestId":...}` shape.
+    pub(crate) fn from_response_value(value: &serde_json::Value) -> Option<Self> {
+        let response = value
+            .get("Response")
+            .filter(|response| response.get("Error").is_some())
+            .unwrap_or(value);
+        let error = response.get("Error")?;
+        let code = error
+            .get("Code")
+            .and_then(|c| c.as_str())
+            .unwrap_or("Unknown");
+        let message = error
+            .get("Message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error");
+        let request_id = response.get("RequestId").and_then(|r| r.as_str());
+        Some(Self::api_with_request_id(code, message, request_id))
+    }
+
     /// Get error code if this is an API error
     pub fn code(&self) -> Option<&str> {
         match self {
@@ -116,7 +167,7 @@ impl TencentCloudError {
     /// Get request ID if available
     pub fn request_id(&self) -> Option<&str> {
         match self {
-            Self::Api { request_id, .. } => request_id.as_deref(),
+            Self::Api { request_id, .. } => request_id.as_ref().map(RequestId::as_str),
             _ => None,
         }
     }
@@ -139,6 +190,93 @@ impl TencentCloudError {
         matches!(self, Self::Timeout(_))
     }
 
+    /// Check if this is a local quota error
+    pub fn is_quota_error(&self) -> bool {
+        matches!(self, Self::Quota(_))
+    }
+
+    /// Check if the failed request may succeed when retried
+    ///
+    /// True for network errors, timeouts, internal server errors and request
+    /// throttling.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout(_) => true,
+            Self::Api { code, .. } => {
+                code.starts_with("InternalError") || code == "RequestLimitExceeded"
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the HTTP status a web handler should return for this error
+    ///
+    /// * 400 for invalid parameters, locally or from the API
+    /// * 401 for authentication failures, 403 for unauthorized operations and
+    ///   policy vetoes
+    /// * 429 for local quotas and API rate limits
+    /// * 502 for network errors, malformed responses and API internal errors
+    /// * 503 for timeouts
+    /// * 500 for everything else, including local configuration errors
+    pub fn suggested_http_status(&self) -> u16 {
+        match self {
+            Self::Parameter(_) => 400,
+            Self::Auth(_) => 401,
+            Self::Policy(_) => 403,
+            Self::Quota(_) => 429,
+            Self::Network(_) | Self::Json(_) => 502,
+            Self::Timeout(_) => 503,
+            Self::Api { code, .. } => {
+                let category = code.split('.').next().unwrap_or_default();
+                match category {
+                    "InvalidParameter" | "InvalidParameterValue" | "MissingParameter" => 400,
+                    "AuthFailure" => 401,
+                    "UnauthorizedOperation" => 403,
+                    "RequestLimitExceeded" | "LimitExceeded" => 429,
+                    "InternalError" => 502,
+                    _ => 500,
+                }
+            }
+            Self::Config(_) | Self::Signature(_) | Self::Other(_) => 500,
+        }
+    }
+
+    /// Replace every occurrence of the given secrets in the error message
+    ///
+    /// Empty secrets are ignored. Network and JSON errors are returned
+    /// unchanged, as their messages come from the transport and parser and
+    /// never include request headers.
+    pub(crate) fn redact(self, secrets: &[&str]) -> Self {
+        let scrub = |mut text: String| {
+            for secret in secrets.iter().filter(|secret| !secret.is_empty()) {
+                if text.contains(secret) {
+                    text = text.replace(secret, "REDACTED");
+                }
+            }
+            text
+        };
+        match self {
+            Self::Api {
+                code,
+                message,
+                request_id,
+            } => Self::Api {
+                code: scrub(code),
+                message: scrub(message),
+                request_id,
+            },
+            Self::Auth(message) => Self::Auth(scrub(message)),
+            Self::Config(message) => Self::Config(scrub(message)),
+            Self::Parameter(message) => Self::Parameter(scrub(message)),
+            Self::Signature(message) => Self::Signature(scrub(message)),
+            Self::Timeout(message) => Self::Timeout(scrub(message)),
+            Self::Quota(message) => Self::Quota(scrub(message)),
+            Self::Policy(message) => Self::Policy(scrub(message)),
+            Self::Other(message) => Self::Other(scrub(message)),
+            other => other,
+        }
+    }
+
     /// Print all error details (similar to C++ SDK)
     pub fn print_all(&self) -> String {
         match self {
@@ -158,6 +296,94 @@ impl TencentCloudError {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let error = TencentCloudError::signature("bad key s3cr3t-key, token tok3n").redact(&[
+            "s3cr3t-key",
+            "tok3n",
+            "",
+        ]);
+        assert_eq!(
+            error.to_string(),
+            "Signature error: bad key REDACTED, token REDACTED"
+        );
+
+        let error = TencentCloudError::api_with_request_id(
+            "AuthFailure.SignatureFailure",
+            "key s3cr3t-key rejected",
+            Some("req-1"),
+        )
+        .redact(&["s3cr3t-key"]);
+        assert!(!error.print_all().contains("s3cr3t-key"));
+        assert_eq!(error.request_id(), Some("req-1"));
+    }
+
+    #[test]
+    fn test_suggested_http_status() {
+        let api = |code: &str| TencentCloudError::api(code, "message");
+        for (error, status) in [
+            (TencentCloudError::parameter("bad"), 400),
+            (api("InvalidParameterValue.IncorrectPhoneNumber"), 400),
+            (TencentCloudError::auth("bad key"), 401),
+            (api("AuthFailure.SignatureFailure"), 401),
+            (api("UnauthorizedOperation.SmsSdkAppIdVerifyFail"), 403),
+            (TencentCloudError::quota("minute quota"), 429),
+            (api("LimitExceeded.DeliveryFrequencyLimit"), 429),
+            (api("RequestLimitExceeded"), 429),
+            (api("InternalError.Timeout"), 502),
+            (TencentCloudError::timeout("slow"), 503),
+            (api("FailedOperation.InsufficientBalanceInSmsPackage"), 500),
+            (TencentCloudError::config("bad header"), 500),
+            (TencentCloudError::other("unknown"), 500),
+        ] {
+            assert_eq!(error.suggested_http_status(), status, "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_from_response_json() {
+        let error = TencentCloudError::from_response_json(
+            r#"{"Response":{"Error":{"Code":"AuthFailure.SecretIdNotFound","Message":"no such id"},"RequestId":"req-1"}}"#,
+        )
+        .unwrap();
+        assert_eq!(error.code(), Some("AuthFailure.SecretIdNotFound"));
+        assert_eq!(error.request_id(), Some("req-1"));
+        assert!(error.to_string().contains("no such id"));
+
+        assert!(TencentCloudError::from_response_json(
+            r#"{"Response":{"SendStatusSet":[],"RequestId":"req-2"}}"#
+        )
+        .is_none());
+        assert!(TencentCloudError::from_response_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_from_response_json_legacy_shape() {
+        let enveloped = TencentCloudError::from_response_json(
+            r#"{"Response":{"Error":{"Code":"InvalidParameter","Message":"bad"},"RequestId":"req-1"}}"#,
+        )
+        .unwrap();
+        let legacy = TencentCloudError::from_response_json(
+            r#"{"Error":{"Code":"InvalidParameter","Message":"bad"},"RequestId":"req-1"}"#,
+        )
+        .unwrap();
+        assert_eq!(enveloped.print_all(), legacy.print_all());
+        assert_eq!(legacy.code(), Some("InvalidParameter"));
+        assert_eq!(legacy.request_id(), Some("req-1"));
+
+        // The enveloped form wins when both are present
+        let both = TencentCloudError::from_response_json(
+            r#"{"Error":{"Code":"Legacy","Message":"old"},"Response":{"Error":{"Code":"Current","Message":"new"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(both.code(), Some("Current"));
+    }
+}
+
 /// Common API error codes
 pub mod error_codes {
     /// Failed operation - signature incorrect or unapproved
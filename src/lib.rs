@@ -46,9 +46,16 @@ pub mod error;
 pub mod sms;
 
 // Re-export main types for convenient usage
-pub use crate::core::{Client, ClientProfile, Credential, HttpProfile};
+pub use crate::core::{
+    default_client, init_default_client, send_sms, BackoffStrategy, Client, ClientPool,
+    ClientProfile, ConstantBackoff, Credential, DecorrelatedJitter, ExponentialBackoff,
+    HttpProfile, RateLimitInfo, RawResponse, RequestId, RetryProfile, Scheme,
+};
 pub use crate::error::{Result, TencentCloudError};
-pub use crate::sms::{SendSmsRequest, SendSmsResponse, SendStatus};
+pub use crate::sms::{
+    Failure, SendSmsRequest, SendSmsRequestBuilder, SendSmsResponse, SendStatus, SendStatusCode,
+    ValidationError,
+};
 
 /// Initialize the SDK (placeholder for future initialization needs)
 pub fn init_api() {
@@ -56,6 +63,9 @@ pub fn init_api() {
 }
 
 /// Shutdown the SDK (placeholder for future cleanup needs)
+///
+/// To drain the requests of a client before exiting, use
+/// [`Client::shutdown`].
 pub fn shutdown_api() {
     // Currently no cleanup needed, but keeping for API compatibility
 }
@@ -0,0 +1,356 @@
+//! Options and results for sending large recipient lists in chunks
+
+use crate::error::TencentCloudError;
+use crate::sms::segments::{SegmentEstimate, SmsEncoding};
+use crate::sms::{SendSmsRequest, SendSmsResponse};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of phone numbers the API accepts in one SendSms call
+pub const MAX_PHONE_NUMBERS_PER_REQUEST: usize = 200;
+
+/// Options for [`Client::send_sms_all`](crate::Client::send_sms_all)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOptions {
+    /// Phone numbers per SendSms call (default: 200)
+    pub chunk_size: usize,
+    /// Stop sending once the fraction of failed chunks exceeds this rate
+    pub abort_on_failure_rate: Option<f32>,
+    /// Template text used to estimate the segments of each chunk
+    pub template_content: Option<String>,
+}
+
+impl BatchOptions {
+    /// Create new batch options with default settings
+    pub fn new() -> Self {
+        Self {
+            chunk_size: MAX_PHONE_NUMBERS_PER_REQUEST,
+            abort_on_failure_rate: None,
+            template_content: None,
+        }
+    }
+
+    /// Set the number of phone numbers per SendSms call
+    ///
+    /// Values are clamped to between 1 and 200.
+    pub fn set_chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_PHONE_NUMBERS_PER_REQUEST);
+        self
+    }
+
+    /// Set the failure rate above which no further chunks are sent
+    ///
+    /// After each chunk the failure rate is recomputed as failed chunks over
+    /// chunks sent so far. Once it is strictly greater than `rate`, the
+    /// remaining chunks are skipped and the result is marked as aborted. A
+    /// chunk fails when its SendSms call returns an error; per-number failures
+    /// inside a successful response do not count. Because the rate is taken
+    /// over the chunks sent so far, a failing first chunk aborts the batch for
+    /// any rate below 1.0.
+    pub fn set_abort_on_failure_rate(&mut self, rate: f32) -> &mut Self {
+        self.abort_on_failure_rate = Some(rate);
+        self
+    }
+
+    /// Set the template text used to estimate the segments of each chunk
+    ///
+    /// Without it, [`ChunkResult::segments`] is estimated from the signature
+    /// and template parameters alone. Sending is not affected.
+    pub fn set_template_content<S: Into<String>>(&mut self, template_content: S) -> &mut Self {
+        self.template_content = Some(template_content.into());
+        self
+    }
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of sending one chunk of a batch
+#[derive(Debug)]
+pub struct ChunkResult {
+    /// Phone numbers in this chunk
+    pub phone_numbers: Vec<String>,
+    /// Response or error of the SendSms call
+    pub result: Result<SendSmsResponse, TencentCloudError>,
+    /// Estimated encoding and segments of the message sent to each number
+    pub segments: SegmentEstimate,
+}
+
+/// Early stop of a batch, with the phone numbers left unsent
+#[derive(Debug)]
+pub struct BatchStop {
+    /// Phone numbers not sent because the batch stopped
+    pub unsent: Vec<String>,
+    /// Whether the batch was cancelled
+    pub cancelled: bool,
+    /// Error that stopped the batch, such as an exceeded quota
+    ///
+    /// `None` when the batch was cancelled or stopped on the failure-rate
+    /// threshold.
+    pub stopped_by: Option<TencentCloudError>,
+}
+
+/// Item of [`Client::send_sms_stream`](crate::Client::send_sms_stream)
+#[derive(Debug)]
+pub enum BatchEvent {
+    /// A chunk was sent
+    Chunk(ChunkResult),
+    /// The batch stopped before all numbers were sent; always the last item
+    Stopped(BatchStop),
+}
+
+/// Remaining work of a chunked send, advanced one chunk at a time
+#[derive(Debug)]
+pub(crate) struct BatchRun {
+    /// Request whose fields are copied to every chunk
+    pub(crate) request: SendSmsRequest,
+    pub(crate) options: BatchOptions,
+    /// Phone numbers not yet sent
+    phones: Vec<String>,
+    sent: usize,
+    failed: usize,
+    /// Stop to report after the chunk that triggered it
+    pending_stop: Option<BatchStop>,
+    finished: bool,
+}
+
+impl BatchRun {
+    /// Start a chunked send of all phone numbers of `request`
+    pub(crate) fn new(request: SendSmsRequest, options: &BatchOptions) -> Self {
+        Self {
+            phones: request.phone_number_set.clone(),
+            request,
+            options: options.clone(),
+            sent: 0,
+            failed: 0,
+            pending_stop: None,
+            finished: false,
+        }
+    }
+
+    /// Take the stop scheduled by the previous chunk, if any
+    pub(crate) fn take_pending_stop(&mut self) -> Option<BatchEvent> {
+        self.pending_stop.take().map(BatchEvent::Stopped)
+    }
+
+    /// Take the next chunk of phone numbers, or `None` once finished
+    pub(crate) fn take_chunk(&mut self) -> Option<Vec<String>> {
+        if self.finished || self.phones.is_empty() {
+            self.finished = true;
+            return None;
+        }
+        let rest = self
+            .phones
+            .split_off(self.options.chunk_size.min(self.phones.len()).max(1));
+        Some(std::mem::replace(&mut self.phones, rest))
+    }
+
+    /// Stop before sending `chunk`, which is reported as unsent
+    pub(crate) fn stop(
+        &mut self,
+        chunk: Vec<String>,
+        cancelled: bool,
+        stopped_by: Option<TencentCloudError>,
+    ) -> BatchEvent {
+        self.phones.splice(0..0, chunk);
+        self.finished = true;
+        BatchEvent::Stopped(BatchStop {
+            unsent: std::mem::take(&mut self.phones),
+            cancelled,
+            stopped_by,
+        })
+    }
+
+    /// Record a sent chunk, scheduling a stop if the failure rate is exceeded
+    pub(crate) fn record(&mut self, chunk: ChunkResult) -> BatchEvent {
+        self.sent += 1;
+        if chunk.result.is_err() {
+            self.failed += 1;
+        }
+        if let Some(threshold) = self.options.abort_on_failure_rate {
+            if !self.phones.is_empty() && self.failed as f32 / self.sent as f32 > threshold {
+                self.finished = true;
+                self.pending_stop = Some(BatchStop {
+                    unsent: std::mem::take(&mut self.phones),
+                    cancelled: false,
+                    stopped_by: None,
+                });
+            }
+        }
+        BatchEvent::Chunk(chunk)
+    }
+}
+
+/// Result of [`Client::send_sms_all`](crate::Client::send_sms_all)
+#[derive(Debug, Default)]
+pub struct BatchSendResult {
+    /// Chunks sent, in order
+    pub chunks: Vec<ChunkResult>,
+    /// Phone numbers not sent because the batch was aborted
+    pub unsent: Vec<String>,
+    /// Whether the batch stopped early
+    pub aborted: bool,
+    /// Whether the batch stopped early because it was cancelled
+    pub cancelled: bool,
+    /// Error that stopped the batch, such as an exceeded quota
+    ///
+    /// `None` when the batch completed, was cancelled or stopped on the
+    /// failure-rate threshold.
+    pub stopped_by: Option<TencentCloudError>,
+}
+
+impl BatchSendResult {
+    /// Get the number of chunks whose SendSms call failed
+    pub fn failed_chunks(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.result.is_err())
+            .count()
+    }
+
+    /// Get the fraction of sent chunks that failed
+    pub fn failure_rate(&self) -> f32 {
+        if self.chunks.is_empty() {
+            0.0
+        } else {
+            self.failed_chunks() as f32 / self.chunks.len() as f32
+        }
+    }
+
+    /// Get the responses of the successful chunks
+    pub fn responses(&self) -> impl Iterator<Item = &SendSmsResponse> {
+        self.chunks
+            .iter()
+            .filter_map(|chunk| chunk.result.as_ref().ok())
+    }
+
+    /// Get the estimated segments sent per encoding, over all chunks sent
+    ///
+    /// Each chunk contributes its segments per message times its number of
+    /// phone numbers, whether or not its SendSms call succeeded.
+    pub fn segments_by_encoding(&self) -> HashMap<SmsEncoding, usize> {
+        let mut totals = HashMap::new();
+        for chunk in &self.chunks {
+            *totals.entry(chunk.segments.encoding).or_insert(0) +=
+                chunk.segments.segments * chunk.phone_numbers.len();
+        }
+        totals
+    }
+
+    /// Get serial numbers that appear more than once across all chunks
+    ///
+    /// Every message should get a unique `SerialNo`, so a duplicate points to
+    /// a double send or a backend fault and should be checked against billing.
+    /// Empty serial numbers, as returned for failed sends, are ignored. Each
+    /// duplicate is listed once, in order of its second appearance.
+    pub fn duplicate_serials(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        for status in self
+            .responses()
+            .flat_map(|response| &response.send_status_set)
+        {
+            let serial = status.serial_no.as_str();
+            if !serial.is_empty() && !seen.insert(serial) && !duplicates.contains(&serial) {
+                duplicates.push(serial);
+            }
+        }
+        duplicates.into_iter().map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sms::SendStatus;
+
+    #[test]
+    fn test_batch_options() {
+        let mut options = BatchOptions::new();
+        assert_eq!(options.chunk_size, 200);
+        assert_eq!(options.abort_on_failure_rate, None);
+
+        options.set_chunk_size(0).set_abort_on_failure_rate(0.5);
+        assert_eq!(options.chunk_size, 1);
+        assert_eq!(options.abort_on_failure_rate, Some(0.5));
+
+        options.set_chunk_size(1000);
+        assert_eq!(options.chunk_size, 200);
+    }
+
+    #[test]
+    fn test_duplicate_serials() {
+        let response = |serials: &[&str]| SendSmsResponse {
+            send_status_set: serials
+                .iter()
+                .map(|serial| SendStatus {
+                    serial_no: serial.to_string(),
+                    phone_number: "+8613800000000".to_string(),
+                    fee: 1,
+                    session_context: None,
+                    code: "Ok".to_string(),
+                    message: String::new(),
+                    iso_code: "CN".to_string(),
+                })
+                .collect(),
+            request_id: "test-request-id".into(),
+        };
+        let chunk = |serials: &[&str]| ChunkResult {
+            phone_numbers: Vec::new(),
+            result: Ok(response(serials)),
+            segments: SegmentEstimate::for_text(""),
+        };
+        let result = BatchSendResult {
+            chunks: vec![
+                chunk(&["2019:1", "2019:2", ""]),
+                chunk(&["2019:3", "2019:2", ""]),
+                ChunkResult {
+                    phone_numbers: Vec::new(),
+                    result: Err(TencentCloudError::other("failed")),
+                    segments: SegmentEstimate::for_text(""),
+                },
+                chunk(&["2019:2", "2019:1"]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(result.duplicate_serials(), vec!["2019:2", "2019:1"]);
+        assert!(BatchSendResult::default().duplicate_serials().is_empty());
+    }
+
+    #[test]
+    fn test_segments_by_encoding() {
+        let chunk = |phones: usize, text: &str| ChunkResult {
+            phone_numbers: vec!["+8613800000000".to_string(); phones],
+            result: Err(TencentCloudError::other("not sent")),
+            segments: SegmentEstimate::for_text(text),
+        };
+        let result = BatchSendResult {
+            chunks: vec![
+                chunk(3, "Your code is 123456"),
+                chunk(2, &"您的验证码".repeat(20)),
+                chunk(1, "Reminder: appointment at 10:00"),
+            ],
+            ..Default::default()
+        };
+
+        let tags: Vec<_> = result
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.segments.encoding, chunk.segments.segments))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                (SmsEncoding::Gsm7, 1),
+                (SmsEncoding::Ucs2, 2),
+                (SmsEncoding::Gsm7, 1)
+            ]
+        );
+        let totals = result.segments_by_encoding();
+        assert_eq!(totals[&SmsEncoding::Gsm7], 4);
+        assert_eq!(totals[&SmsEncoding::Ucs2], 4);
+    }
+}
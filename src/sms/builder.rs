@@ -0,0 +1,238 @@
+//! Builder for SMS send requests
+
+use crate::error::{Result, TencentCloudError};
+use crate::sms::models::{check_extend_code, check_phone_numbers, SendSmsRequest, ValidationError};
+
+/// Builder for [`SendSmsRequest`]
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::SendSmsRequest;
+///
+/// let request = SendSmsRequest::builder()
+///     .phone_number("+8613800000000")
+///     .sms_sdk_app_id("1400000000")
+///     .template_id("123456")
+///     .sign_name("YourSignature")
+///     .template_params(vec!["123456".to_string()])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SendSmsRequestBuilder {
+    phone_number_set: Vec<String>,
+    sms_sdk_app_id: Option<String>,
+    template_id: Option<String>,
+    sign_name: Option<String>,
+    template_param_set: Vec<String>,
+    extend_code: Option<String>,
+    session_context: Option<String>,
+    sender_id: Option<String>,
+}
+
+impl SendSmsRequestBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single phone number
+    pub fn phone_number<S: Into<String>>(mut self, phone: S) -> Self {
+        self.phone_number_set.push(phone.into());
+        self
+    }
+
+    /// Set the list of phone numbers, replacing any added so far
+    pub fn phone_numbers(mut self, phones: Vec<String>) -> Self {
+        self.phone_number_set = phones;
+        self
+    }
+
+    /// Set the SMS SDK App ID
+    pub fn sms_sdk_app_id<S: Into<String>>(mut self, sms_sdk_app_id: S) -> Self {
+        self.sms_sdk_app_id = Some(sms_sdk_app_id.into());
+        self
+    }
+
+    /// Set the template ID
+    pub fn template_id<S: Into<String>>(mut self, template_id: S) -> Self {
+        self.template_id = Some(template_id.into());
+        self
+    }
+
+    /// Set the SMS signature
+    pub fn sign_name<S: Into<String>>(mut self, sign_name: S) -> Self {
+        self.sign_name = Some(sign_name.into());
+        self
+    }
+
+    /// Set the template parameters
+    pub fn template_params(mut self, params: Vec<String>) -> Self {
+        self.template_param_set = params;
+        self
+    }
+
+    /// Set the extension code
+    pub fn extend_code<S: Into<String>>(mut self, extend_code: S) -> Self {
+        self.extend_code = Some(extend_code.into());
+        self
+    }
+
+    /// Set the session context
+    pub fn session_context<S: Into<String>>(mut self, session_context: S) -> Self {
+        self.session_context = Some(session_context.into());
+        self
+    }
+
+    /// Set the sender ID for international SMS
+    pub fn sender_id<S: Into<String>>(mut self, sender_id: S) -> Self {
+        self.sender_id = Some(sender_id.into());
+        self
+    }
+
+    /// Check the fields set so far without consuming the builder
+    ///
+    /// Reports every required field that is still missing, along with invalid
+    /// values among the fields already set, such as a malformed phone number.
+    /// Once all required fields are present, the request-level checks of
+    /// [`SendSmsRequest::validate`] are applied as well.
+    pub fn validate(&self) -> std::result::Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.phone_number_set.is_empty() {
+            errors.push(ValidationError::new(
+                "phone_number_set",
+                "phone_number_set not set",
+            ));
+        }
+        if self.sms_sdk_app_id.is_none() {
+            errors.push(ValidationError::new(
+                "sms_sdk_app_id",
+                "sms_sdk_app_id not set",
+            ));
+        }
+        if self.template_id.is_none() {
+            errors.push(ValidationError::new("template_id", "template_id not set"));
+        }
+
+        if !errors.is_empty() {
+            // Still check the fields that are set, so a partial builder
+            // reports bad values as well as missing ones
+            if let Err(error) = check_phone_numbers(&self.phone_number_set) {
+                errors.push(error);
+            }
+            if let Err(error) =
+                check_extend_code(self.extend_code.as_deref(), self.sender_id.as_deref())
+            {
+                errors.push(error);
+            }
+        } else if let Err(error) = self.to_request().check() {
+            errors.push(error);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validate and build the request
+    pub fn build(self) -> Result<SendSmsRequest> {
+        self.validate().map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            TencentCloudError::parameter(messages.join("; "))
+        })?;
+        Ok(self.to_request())
+    }
+
+    fn to_request(&self) -> SendSmsRequest {
+        let mut request = SendSmsRequest::new_international(
+            self.phone_number_set.clone(),
+            self.sms_sdk_app_id.clone().unwrap_or_default(),
+            self.template_id.clone().unwrap_or_default(),
+            self.template_param_set.clone(),
+        );
+        request.sign_name = self.sign_name.clone();
+        request.extend_code = self.extend_code.clone();
+        request.session_context = self.session_context.clone();
+        request.sender_id = self.sender_id.clone();
+        request
+    }
+}
+
+impl SendSmsRequest {
+    /// Create a builder for a SendSmsRequest
+    pub fn builder() -> SendSmsRequestBuilder {
+        SendSmsRequestBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_build() {
+        let request = SendSmsRequest::builder()
+            .phone_number("+8613800000000")
+            .sms_sdk_app_id("1400000000")
+            .template_id("123456")
+            .sign_name("TestSignature")
+            .template_params(vec!["123456".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(request.phone_number_set, vec!["+8613800000000"]);
+        assert_eq!(request.sign_name, Some("TestSignature".to_string()));
+        assert_eq!(request.template_param_set, Some(vec!["123456".to_string()]));
+    }
+
+    #[test]
+    fn test_builder_validate_partial() {
+        let builder = SendSmsRequest::builder().phone_number("+8613800000000");
+
+        let errors = builder.validate().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["sms_sdk_app_id", "template_id"]);
+        assert_eq!(errors[1].message, "template_id not set");
+
+        // The builder is still usable after validation
+        let builder = builder.sms_sdk_app_id("1400000000").template_id("123456");
+        assert!(builder.validate().is_ok());
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_builder_build_invalid() {
+        let result = SendSmsRequest::builder()
+            .phone_number("12345")
+            .sms_sdk_app_id("1400000000")
+            .template_id("123456")
+            .build();
+
+        assert!(matches!(result, Err(TencentCloudError::Parameter(_))));
+    }
+
+    #[test]
+    fn test_builder_validate_partial_reports_invalid_fields() {
+        let errors = SendSmsRequest::builder()
+            .phone_number("12345")
+            .validate()
+            .unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["sms_sdk_app_id", "template_id", "phone_number_set"]
+        );
+        assert_eq!(errors[2].message, "Invalid phone number format: 12345");
+
+        let errors = SendSmsRequest::builder()
+            .extend_code("01")
+            .sender_id("Tencent")
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors.last().unwrap().field, "extend_code");
+    }
+}
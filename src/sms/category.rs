@@ -0,0 +1,85 @@
+//! SMS categories and local send policies
+
+use crate::sms::SendSmsRequest;
+use chrono::{DateTime, FixedOffset, Timelike, Utc};
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Intent of an SMS, which determines the compliance rules it falls under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmsCategory {
+    /// Verification codes
+    Verification,
+    /// Transactional notifications
+    Notification,
+    /// Marketing messages
+    Marketing,
+}
+
+/// Local policy deciding whether a categorized send may proceed
+///
+/// Set on [`ClientProfile::set_category_policy`](crate::ClientProfile::set_category_policy);
+/// the client consults it before sending any request with a category.
+pub trait CategoryPolicy: Debug + Send + Sync {
+    /// Allow the send, or veto it with the reason
+    fn check(&self, category: SmsCategory, request: &SendSmsRequest) -> Result<(), String>;
+}
+
+/// Policy that only allows marketing messages during set local hours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketingHoursPolicy {
+    /// Hours of the day during which marketing sends are allowed, e.g. `9..21`
+    pub allowed_hours: Range<u32>,
+    /// Offset of the recipients' local time from UTC
+    pub offset: FixedOffset,
+}
+
+impl MarketingHoursPolicy {
+    /// Create a new policy allowing marketing sends during `allowed_hours` at `offset`
+    pub fn new(allowed_hours: Range<u32>, offset: FixedOffset) -> Self {
+        Self {
+            allowed_hours,
+            offset,
+        }
+    }
+
+    /// Check if a message of `category` may be sent at `time`
+    pub fn allows_at(&self, category: SmsCategory, time: DateTime<Utc>) -> bool {
+        category != SmsCategory::Marketing
+            || self
+                .allowed_hours
+                .contains(&time.with_timezone(&self.offset).hour())
+    }
+}
+
+impl CategoryPolicy for MarketingHoursPolicy {
+    fn check(&self, category: SmsCategory, _request: &SendSmsRequest) -> Result<(), String> {
+        if self.allows_at(category, Utc::now()) {
+            Ok(())
+        } else {
+            Err(format!(
+                "marketing messages are only allowed between {}:00 and {}:00",
+                self.allowed_hours.start, self.allowed_hours.end
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_marketing_hours_policy() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        let policy = MarketingHoursPolicy::new(9..21, beijing);
+
+        // 02:00 UTC is 10:00 in Beijing, 14:00 UTC is 22:00
+        let morning = Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap();
+        let night = Utc.with_ymd_and_hms(2021, 1, 1, 14, 0, 0).unwrap();
+        assert!(policy.allows_at(SmsCategory::Marketing, morning));
+        assert!(!policy.allows_at(SmsCategory::Marketing, night));
+        assert!(policy.allows_at(SmsCategory::Verification, night));
+    }
+}
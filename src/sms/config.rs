@@ -0,0 +1,162 @@
+//! Config-file-defined defaults for SMS send requests
+
+use crate::sms::models::SendSmsRequest;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "config")]
+use crate::error::{Result, TencentCloudError};
+
+/// Reusable send settings loaded from configuration
+///
+/// Holds everything about a send except the recipients and template
+/// parameters, so app IDs, templates and signatures can live in per-environment
+/// config files rather than in code. With the `config` feature enabled it can be
+/// loaded from TOML:
+///
+/// ```toml
+/// sms_sdk_app_id = "1400000000"
+/// template_id = "123456"
+/// sign_name = "YourSignature"
+/// default_params = ["5"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SendSmsTemplate {
+    /// SMS SDK App ID
+    pub sms_sdk_app_id: String,
+
+    /// Template ID
+    pub template_id: String,
+
+    /// SMS signature, required for domestic SMS
+    #[serde(default)]
+    pub sign_name: Option<String>,
+
+    /// Template parameters used when a send supplies none
+    #[serde(default)]
+    pub default_params: Vec<String>,
+
+    /// Default SMS extension code
+    #[serde(default)]
+    pub extend_code: Option<String>,
+
+    /// Default session context
+    #[serde(default)]
+    pub session_context: Option<String>,
+
+    /// Default sender ID for international SMS
+    #[serde(default)]
+    pub sender_id: Option<String>,
+}
+
+impl SendSmsTemplate {
+    /// Load a template from a TOML string
+    #[cfg(feature = "config")]
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| TencentCloudError::config(format!("Invalid SMS template config: {}", e)))
+    }
+
+    /// Load a template from a TOML file
+    #[cfg(feature = "config")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TencentCloudError::config(format!(
+                "Failed to read SMS template config {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_toml_str(&content)
+    }
+
+    /// Create a request for the given recipients and template parameters
+    ///
+    /// Falls back to `default_params` when `params` is empty.
+    pub fn with_recipients_and_params(
+        &self,
+        phone_number_set: Vec<String>,
+        params: Vec<String>,
+    ) -> SendSmsRequest {
+        let params = if params.is_empty() {
+            self.default_params.clone()
+        } else {
+            params
+        };
+
+        let mut request = SendSmsRequest::new_international(
+            phone_number_set,
+            self.sms_sdk_app_id.clone(),
+            self.template_id.clone(),
+            params,
+        );
+        request.sign_name = self.sign_name.clone();
+        request.extend_code = self.extend_code.clone();
+        request.session_context = self.session_context.clone();
+        request.sender_id = self.sender_id.clone();
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> SendSmsTemplate {
+        SendSmsTemplate {
+            sms_sdk_app_id: "1400000000".to_string(),
+            template_id: "123456".to_string(),
+            sign_name: Some("TestSignature".to_string()),
+            default_params: vec!["5".to_string()],
+            extend_code: None,
+            session_context: None,
+            sender_id: None,
+        }
+    }
+
+    #[test]
+    fn test_with_recipients_and_params() {
+        let request = template().with_recipients_and_params(
+            vec!["+8613800000000".to_string()],
+            vec!["123456".to_string()],
+        );
+        assert_eq!(request.sms_sdk_app_id, "1400000000");
+        assert_eq!(request.template_id, "123456");
+        assert_eq!(request.sign_name, Some("TestSignature".to_string()));
+        assert_eq!(request.template_param_set, Some(vec!["123456".to_string()]));
+        assert!(request.validate().is_ok());
+
+        let request =
+            template().with_recipients_and_params(vec!["+8613800000000".to_string()], vec![]);
+        assert_eq!(request.template_param_set, Some(vec!["5".to_string()]));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_toml_str() {
+        let loaded = SendSmsTemplate::from_toml_str(
+            r#"
+            sms_sdk_app_id = "1400000000"
+            template_id = "123456"
+            sign_name = "TestSignature"
+            default_params = ["5"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(loaded, template());
+
+        let request = loaded.with_recipients_and_params(
+            vec!["+8613800000000".to_string()],
+            vec!["123456".to_string(), "5".to_string()],
+        );
+        assert_eq!(
+            request.template_param_set,
+            Some(vec!["123456".to_string(), "5".to_string()])
+        );
+
+        assert!(matches!(
+            SendSmsTemplate::from_toml_str("template_id = 1"),
+            Err(TencentCloudError::Config(_))
+        ));
+    }
+}
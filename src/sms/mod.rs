@@ -1,5 +1,36 @@
 //! SMS service models and types
 
+pub mod batch;
+pub mod builder;
+pub mod category;
+pub mod config;
 pub mod models;
+pub mod params;
+pub mod phone;
+pub mod registry;
+pub mod segments;
+pub mod sign;
+pub mod status;
+pub mod template;
 
+pub use batch::{BatchEvent, BatchOptions, BatchSendResult, BatchStop, ChunkResult};
+pub use builder::SendSmsRequestBuilder;
+pub use category::{CategoryPolicy, MarketingHoursPolicy, SmsCategory};
+pub use config::SendSmsTemplate;
 pub use models::*;
+pub use params::{IntoTemplateParams, NamedParams};
+pub use phone::{is_e164, nation_code, requires_sender_id, to_e164};
+pub use registry::{TemplateEntry, TemplateRegistry};
+pub use segments::{CostEstimate, SegmentEstimate, SmsEncoding};
+pub use sign::{
+    AddSignStatus, AddSmsSignRequest, AddSmsSignResponse, DescribeSignListStatus,
+    DescribeSmsSignListRequest, DescribeSmsSignListResponse, DocumentType, RejectionReason,
+    SignPurpose, SignStatus, SignType, MAX_SIGN_NAME_CHARS, MIN_SIGN_NAME_CHARS,
+};
+pub use status::{
+    DeliveryReport, DeliveryResult, DeliveryState, PullSmsSendStatus,
+    PullSmsSendStatusByPhoneNumberRequest, PullSmsSendStatusByPhoneNumberResponse,
+};
+pub use template::{
+    DescribeSmsTemplateListRequest, DescribeSmsTemplateListResponse, DescribeTemplateListStatus,
+};
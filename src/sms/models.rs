@@ -1,6 +1,17 @@
 //! SMS service models and data structures
 
+use crate::core::response::RequestId;
+use crate::error::TencentCloudError;
+use crate::sms::category::SmsCategory;
+use crate::sms::params::IntoTemplateParams;
+use crate::sms::phone::{is_e164, nation_code, requires_sender_id, to_e164};
+use crate::sms::segments::SegmentEstimate;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+/// Default maximum serialized size of a request body, in bytes
+pub const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
 
 /// Request structure for sending SMS
 #[derive(Debug, Clone, Serialize)]
@@ -19,6 +30,8 @@ pub struct SendSmsRequest {
 
     /// Template ID
     /// You must use an approved template ID
+    /// Usually numeric, but some international templates use alphanumeric IDs,
+    /// so only an empty ID is rejected by validation
     #[serde(rename = "TemplateId")]
     pub template_id: String,
 
@@ -35,6 +48,7 @@ pub struct SendSmsRequest {
 
     /// SMS extension code
     /// Default is not enabled
+    /// Not permitted together with `sender_id`
     #[serde(rename = "ExtendCode", skip_serializing_if = "Option::is_none")]
     pub extend_code: Option<String>,
 
@@ -45,8 +59,14 @@ pub struct SendSmsRequest {
 
     /// SenderId for international SMS
     /// Required for international SMS with independent SenderId
+    /// Not permitted together with `extend_code`
     #[serde(rename = "SenderId", skip_serializing_if = "Option::is_none")]
     pub sender_id: Option<String>,
+
+    /// Category of the message, used only for local send policies
+    /// Not sent to the API
+    #[serde(skip)]
+    pub category: Option<SmsCategory>,
 }
 
 impl SendSmsRequest {
@@ -93,9 +113,30 @@ impl SendSmsRequest {
             extend_code: None,
             session_context: None,
             sender_id: None,
+            category: None,
         }
     }
 
+    /// Create a new SendSmsRequest with typed template parameters
+    ///
+    /// Same as [`SendSmsRequest::new`], but the parameters come from any
+    /// [`IntoTemplateParams`] value, such as a struct that fixes their order.
+    pub fn with_params<S: Into<String>, T: IntoTemplateParams>(
+        phone_number_set: Vec<String>,
+        sms_sdk_app_id: S,
+        template_id: S,
+        sign_name: S,
+        params: T,
+    ) -> Self {
+        Self::new(
+            phone_number_set,
+            sms_sdk_app_id,
+            template_id,
+            sign_name,
+            params.into_params(),
+        )
+    }
+
     /// Create a new SendSmsRequest for international SMS
     pub fn new_international<S: Into<String>>(
         phone_number_set: Vec<String>,
@@ -116,9 +157,16 @@ impl SendSmsRequest {
             extend_code: None,
             session_context: None,
             sender_id: None,
+            category: None,
         }
     }
 
+    /// Set the category of the message for local send policies
+    pub fn with_category(mut self, category: SmsCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
     /// Set the SMS signature
     pub fn set_sign_name<S: Into<String>>(&mut self, sign_name: S) -> &mut Self {
         self.sign_name = Some(sign_name.into());
@@ -148,42 +196,571 @@ impl SendSmsRequest {
     }
 
     /// Set sender ID for international SMS
+    ///
+    /// Alphanumeric sender IDs and numeric short codes such as `"12345"` are
+    /// both accepted. The sender ID is never checked as a phone number: the
+    /// E.164 checks of [`SendSmsRequest::validate`] apply to recipients only.
     pub fn set_sender_id<S: Into<String>>(&mut self, sender_id: S) -> &mut Self {
         self.sender_id = Some(sender_id.into());
         self
     }
 
+    /// Add a recipient phone number
+    ///
+    /// The 200-number limit is checked by [`SendSmsRequest::validate`], not here.
+    pub fn add_recipient<S: Into<String>>(&mut self, phone: S) -> &mut Self {
+        self.phone_number_set.push(phone.into());
+        self
+    }
+
+    /// Add several recipient phone numbers
+    pub fn add_recipients<I, S>(&mut self, phones: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.phone_number_set
+            .extend(phones.into_iter().map(Into::into));
+        self
+    }
+
+    /// Remove phone numbers that are not in E.164 format, returning them
+    ///
+    /// Lets a best-effort batch proceed when a few entries are malformed.
+    /// Numbers are removed without any error, so callers should inspect the
+    /// returned list rather than assume every recipient will be messaged.
+    /// Numbers without a `+` prefix, which [`SendSmsRequest::validate`] still
+    /// accepts, are dropped as well.
+    pub fn drop_invalid(&mut self) -> Vec<String> {
+        let (valid, dropped) = std::mem::take(&mut self.phone_number_set)
+            .into_iter()
+            .partition(|phone| is_e164(phone));
+        self.phone_number_set = valid;
+        dropped
+    }
+
+    /// Serialize the request for audit logs, without recipients or parameters
+    ///
+    /// Phone numbers are masked down to their country calling code, e.g.
+    /// `+86****`, and a `PhoneNumberCount` field records how many there were.
+    /// Template parameters and the session context are replaced with
+    /// `REDACTED`; the remaining fields are serialized as usual.
+    pub fn to_redacted_json(&self) -> String {
+        let mut redacted = self.clone();
+        redacted.phone_number_set = self
+            .phone_number_set
+            .iter()
+            .map(|phone| match nation_code(phone) {
+                Some(code) => format!("+{}****", code),
+                None => "****".to_string(),
+            })
+            .collect();
+        redacted.template_param_set = self
+            .template_param_set
+            .as_ref()
+            .map(|params| vec!["REDACTED".to_string(); params.len()]);
+        redacted.session_context = self
+            .session_context
+            .as_ref()
+            .map(|_| "REDACTED".to_string());
+
+        let mut value = serde_json::to_value(&redacted).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "PhoneNumberCount".to_string(),
+                self.phone_number_set.len().into(),
+            );
+        }
+        value.to_string()
+    }
+
+    /// Serialize the request, sending absent optional fields as `null`
+    ///
+    /// The derived serialization leaves out unset optional fields; this
+    /// writes each of them as an explicit `null` instead.
+    pub fn to_json_with_nulls(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            for field in [
+                "SignName",
+                "TemplateParamSet",
+                "ExtendCode",
+                "SessionContext",
+                "SenderId",
+            ] {
+                object.entry(field).or_insert(serde_json::Value::Null);
+            }
+        }
+        value
+    }
+
+    /// Flatten the request into the key/value pairs of the API Explorer
+    ///
+    /// Array elements get zero-based index suffixes, e.g. `PhoneNumberSet.0`
+    /// and `TemplateParamSet.1`, matching the form parameters of the online
+    /// API Explorer. Unset optional fields are left out.
+    pub fn to_api_explorer_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(self) {
+            for (name, value) in fields {
+                flatten_param(name, value, &mut params);
+            }
+        }
+        params
+    }
+
+    /// Compute a stable fingerprint of the request for deduplication
+    ///
+    /// Returns the lowercase hex SHA-256 of the app ID, template ID,
+    /// signature, template parameters, extension code, sender ID and the
+    /// recipients sorted lexicographically, so requests that only list their
+    /// recipients in a different order share a fingerprint. The session
+    /// context and category are left out: they do not change what is sent
+    /// to whom. An empty parameter list and no parameters are the same.
+    pub fn fingerprint(&self) -> String {
+        let mut recipients: Vec<&str> = self.phone_number_set.iter().map(String::as_str).collect();
+        recipients.sort_unstable();
+        let normalized = serde_json::json!([
+            self.sms_sdk_app_id,
+            self.template_id,
+            self.sign_name,
+            self.template_param_set.as_deref().unwrap_or_default(),
+            self.extend_code,
+            self.sender_id,
+            recipients,
+        ]);
+        hex::encode(Sha256::digest(normalized.to_string().as_bytes()))
+    }
+
     /// Validate the request parameters
+    ///
+    /// A missing sender ID for a destination that requires one is only
+    /// logged as a warning here; use [`SendSmsRequest::validate_sender_id`]
+    /// to reject it.
     pub fn validate(&self) -> Result<(), String> {
-        if self.phone_number_set.is_empty() {
-            return Err("Phone number set cannot be empty".to_string());
+        self.check().map_err(|e| e.message)?;
+        if let Err(message) = self.validate_sender_id() {
+            log::warn!("{}", message);
+        }
+        Ok(())
+    }
+
+    /// Find recipients listed more than once in different formats
+    ///
+    /// Numbers are canonicalized with [`to_e164`], taking numbers without an
+    /// international prefix to be in `default_nation_code`, so e.g.
+    /// `13800000000` and `+8613800000000` are the same subscriber and would
+    /// be billed twice. Returns each later occurrence paired with the first
+    /// one; exact repeats of the same string are not reported.
+    pub fn cross_format_duplicates(&self, default_nation_code: &str) -> Vec<(String, String)> {
+        let mut first_seen: HashMap<String, &str> = HashMap::new();
+        let mut duplicates = Vec::new();
+        for phone in &self.phone_number_set {
+            let Some(canonical) = to_e164(phone, default_nation_code) else {
+                continue;
+            };
+            match first_seen.get(&canonical) {
+                Some(first) if *first != phone => {
+                    duplicates.push((first.to_string(), phone.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    first_seen.insert(canonical, phone);
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Validate the request and report every problem found
+    ///
+    /// Runs the checks of [`SendSmsRequest::validate`], the sender ID check of
+    /// [`SendSmsRequest::validate_sender_id`] and the duplicate check of
+    /// [`SendSmsRequest::cross_format_duplicates`], collecting their errors
+    /// instead of stopping at the first.
+    pub fn validate_all(&self, default_nation_code: &str) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        if let Err(error) = self.check() {
+            errors.push(error);
+        }
+        if let Err(message) = self.validate_sender_id() {
+            errors.push(ValidationError::new("sender_id", message));
+        }
+        for (first, duplicate) in self.cross_format_duplicates(default_nation_code) {
+            errors.push(ValidationError::new(
+                "phone_number_set",
+                format!(
+                    "{} is the same number as {} in another format and would be billed twice",
+                    duplicate, first
+                ),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validate that a sender ID is set if any destination requires one
+    ///
+    /// See [`requires_sender_id`](crate::sms::requires_sender_id) for the
+    /// destinations checked.
+    pub fn validate_sender_id(&self) -> Result<(), String> {
+        if self.sender_id.is_some() {
+            return Ok(());
+        }
+        match self
+            .phone_number_set
+            .iter()
+            .find(|phone| requires_sender_id(phone))
+        {
+            Some(phone) => Err(format!("Sender ID is required for messages to {}", phone)),
+            None => Ok(()),
         }
+    }
 
-        if self.phone_number_set.len() > 200 {
-            return Err("Phone number set cannot exceed 200 numbers".to_string());
+    /// Validate the request parameters, reporting the offending field
+    pub(crate) fn check(&self) -> Result<(), ValidationError> {
+        if self.phone_number_set.is_empty() {
+            return Err(ValidationError::new(
+                "phone_number_set",
+                "Phone number set cannot be empty",
+            ));
         }
 
+        check_phone_numbers(&self.phone_number_set)?;
+
         if self.sms_sdk_app_id.is_empty() {
-            return Err("SMS SDK App ID cannot be empty".to_string());
+            return Err(ValidationError::new(
+                "sms_sdk_app_id",
+                "SMS SDK App ID cannot be empty",
+            ));
         }
 
         if self.template_id.is_empty() {
-            return Err("Template ID cannot be empty".to_string());
+            return Err(ValidationError::new(
+                "template_id",
+                "Template ID cannot be empty",
+            ));
         }
 
-        // Validate phone number format
-        for phone in &self.phone_number_set {
-            if !phone.starts_with('+') && !phone.starts_with("0086") && !phone.starts_with("86") {
-                if phone.len() != 11 {
-                    return Err(format!("Invalid phone number format: {}", phone));
-                }
+        // An empty signature is sent as-is and rejected opaquely by the API;
+        // catch it here with a message that tells it apart from a missing one
+        if self
+            .sign_name
+            .as_deref()
+            .is_some_and(|sign_name| sign_name.trim().is_empty())
+            && self
+                .phone_number_set
+                .iter()
+                .any(|phone| nation_code(phone) == Some("86"))
+        {
+            return Err(ValidationError::new(
+                "sign_name",
+                "Sign name is empty; domestic (+86) numbers require a non-empty signature",
+            ));
+        }
+
+        check_extend_code(self.extend_code.as_deref(), self.sender_id.as_deref())?;
+
+        self.check_body_size(MAX_REQUEST_BODY_SIZE)
+    }
+
+    /// Validate that the serialized request body does not exceed `max_bytes`
+    ///
+    /// [`SendSmsRequest::validate`] applies this check with
+    /// [`MAX_REQUEST_BODY_SIZE`]; use this method to enforce a stricter limit.
+    pub fn validate_body_size(&self, max_bytes: usize) -> Result<(), String> {
+        self.check_body_size(max_bytes).map_err(|e| e.message)
+    }
+
+    fn check_body_size(&self, max_bytes: usize) -> Result<(), ValidationError> {
+        let size = serde_json::to_vec(self).map(|body| body.len()).unwrap_or(0);
+        if size > max_bytes {
+            return Err(ValidationError::new(
+                "request",
+                format!(
+                    "Request body is {} bytes, exceeding the limit of {} bytes; split the phone numbers into smaller batches or shorten the template parameters",
+                    size, max_bytes
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that the request does not span more than `max_nations` countries
+    ///
+    /// Some account tiers limit how many distinct nation codes may appear in a
+    /// single batch. Numbers whose nation code cannot be determined are not
+    /// counted here; their format is checked by [`SendSmsRequest::validate`].
+    pub fn validate_nation_diversity(&self, max_nations: usize) -> Result<(), String> {
+        let nations: HashSet<&str> = self
+            .phone_number_set
+            .iter()
+            .filter_map(|phone| nation_code(phone))
+            .collect();
+
+        if nations.len() > max_nations {
+            let mut nations: Vec<&str> = nations.into_iter().collect();
+            nations.sort_unstable();
+            return Err(format!(
+                "Phone number set spans {} nation codes ({}), exceeding the limit of {}",
+                nations.len(),
+                nations.join(", "),
+                max_nations
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that every template parameter is at most `max` characters long
+    ///
+    /// Limits depend on the template: verification code parameters are
+    /// commonly limited to 6 or 12 characters, while other templates allow
+    /// longer values, so pass the limit that applies to the template in use.
+    pub fn validate_param_lengths(&self, max: usize) -> Result<(), String> {
+        let params = self.template_param_set.as_deref().unwrap_or_default();
+        for (index, param) in params.iter().enumerate() {
+            let length = param.chars().count();
+            if length > max {
+                return Err(format!(
+                    "Template parameter {} ({:?}) is {} characters, exceeding the limit of {}",
+                    index, param, length, max
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the message rendered from `template` fits in `max_chars`
+    ///
+    /// The template parameters are substituted into the `{1}`, `{2}`, ...
+    /// placeholders of `template`, and the result is counted in UCS-2 code
+    /// units as the API does, so a Chinese character counts as one and a
+    /// character outside the Basic Multilingual Plane counts as two.
+    pub fn check_rendered_length(&self, template: &str, max_chars: usize) -> Result<(), String> {
+        let params = self.template_param_set.as_deref().unwrap_or_default();
+        let length = render_template(template, params).encode_utf16().count();
+        if length > max_chars {
+            return Err(format!(
+                "Rendered message is {} characters, exceeding the limit of {}",
+                length, max_chars
+            ));
+        }
+        Ok(())
+    }
+
+    /// Estimate the encoding and segments of the message each recipient gets
+    ///
+    /// The text is `template` with the parameters substituted, or the
+    /// parameters alone when no template is given, which underestimates the
+    /// length. The signature is prepended as `【sign】`, as it is on delivery.
+    pub fn estimate_segments(&self, template: Option<&str>) -> SegmentEstimate {
+        let params = self.template_param_set.as_deref().unwrap_or_default();
+        let body = match template {
+            Some(template) => render_template(template, params),
+            None => params.concat(),
+        };
+        let text = match &self.sign_name {
+            Some(sign_name) => format!("【{}】{}", sign_name, body),
+            None => body,
+        };
+        SegmentEstimate::for_text(&text)
+    }
+
+    /// Check that the parameters match the placeholders of `template_content`
+    ///
+    /// Counts the distinct `{1}`, `{2}`, ... placeholders of the template
+    /// text, so a placeholder used twice counts once, and compares the count
+    /// with the number of template parameters. The placeholders must also be
+    /// numbered `{1}` to `{n}` without gaps, since parameters are matched to
+    /// them by position.
+    pub fn check_against_template(&self, template_content: &str) -> Result<(), String> {
+        let placeholders = template_placeholders(template_content);
+        let params = self.template_param_set.as_ref().map_or(0, Vec::len);
+        if placeholders.len() != params {
+            return Err(format!(
+                "Template has {} placeholders but {} parameters were supplied",
+                placeholders.len(),
+                params
+            ));
+        }
+        if let Some(missing) = (1..=params).find(|index| !placeholders.contains(index)) {
+            return Err(format!(
+                "Template placeholders must be numbered {{1}} to {{{}}}, but {{{}}} is missing",
+                params, missing
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Append `value` under `key`, flattening arrays and objects with dotted keys
+fn flatten_param(key: String, value: serde_json::Value, params: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Null => {}
+        serde_json::Value::String(value) => params.push((key, value)),
+        serde_json::Value::Array(values) => {
+            for (index, value) in values.into_iter().enumerate() {
+                flatten_param(format!("{}.{}", key, index), value, params);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for (name, value) in fields {
+                flatten_param(format!("{}.{}", key, name), value, params);
+            }
+        }
+        value => params.push((key, value.to_string())),
+    }
+}
+
+/// Collect the distinct 1-based `{N}` placeholder indices of `template`
+fn template_placeholders(template: &str) -> HashSet<usize> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .filter_map(|(index, _)| index.parse::<usize>().ok())
+        .filter(|&index| index >= 1)
+        .collect()
+}
+
+/// Substitute `params` into the 1-based `{N}` placeholders of `template`
+///
+/// Placeholders without a matching parameter are left as they are.
+fn render_template(template: &str, params: &[String]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let param = after.find('}').and_then(|end| {
+            let index: usize = after[..end].parse().ok()?;
+            let param = params.get(index.checked_sub(1)?)?;
+            Some((param, end))
+        });
+        match param {
+            Some((param, end)) => {
+                rendered.push_str(param);
+                rest = &after[end + 1..];
             }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// A validation failure for a single request field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Name of the offending field
+    pub field: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+/// Check the size and format of a recipient list
+pub(crate) fn check_phone_numbers(phone_number_set: &[String]) -> Result<(), ValidationError> {
+    if phone_number_set.len() > 200 {
+        return Err(ValidationError::new(
+            "phone_number_set",
+            "Phone number set cannot exceed 200 numbers",
+        ));
+    }
+
+    for phone in phone_number_set {
+        if !phone.starts_with('+')
+            && !phone.starts_with("0086")
+            && !phone.starts_with("86")
+            && phone.len() != 11
+        {
+            return Err(ValidationError::new(
+                "phone_number_set",
+                format!("Invalid phone number format: {}", phone),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that an extension code is not combined with a sender ID
+pub(crate) fn check_extend_code(
+    extend_code: Option<&str>,
+    sender_id: Option<&str>,
+) -> Result<(), ValidationError> {
+    // Extension codes cannot be used with an independent SenderId
+    if sender_id.is_some() && extend_code.is_some() {
+        return Err(ValidationError::new(
+            "extend_code",
+            "Extend code cannot be used together with a sender ID",
+        ));
+    }
+    Ok(())
+}
+
+impl ValidationError {
+    /// Create a new validation error
+    pub fn new<F: Into<String>, M: Into<String>>(field: F, message: M) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
         }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Validate a batch of requests, reporting every invalid one
+///
+/// Returns the index and error of each request that fails
+/// [`SendSmsRequest::validate`], so a long send loop can fail fast before
+/// sending anything.
+pub fn validate_batch(requests: &[SendSmsRequest]) -> Result<(), Vec<(usize, ValidationError)>> {
+    let errors: Vec<(usize, ValidationError)> = requests
+        .iter()
+        .enumerate()
+        .filter_map(|(index, request)| request.check().err().map(|e| (index, e)))
+        .collect();
 
+    if errors.is_empty() {
         Ok(())
+    } else {
+        Err(errors)
     }
 }
 
+/// Sum the fees of several responses, e.g. the chunks of one batch
+pub fn total_fee(responses: &[SendSmsResponse]) -> i32 {
+    responses.iter().map(SendSmsResponse::get_total_fee).sum()
+}
+
+/// Count successful and failed messages across several responses
+///
+/// Returns `(success, failed)`.
+pub fn aggregate_counts(responses: &[SendSmsResponse]) -> (usize, usize) {
+    responses
+        .iter()
+        .fold((0, 0), |(success, failed), response| {
+            (
+                success + response.success_count(),
+                failed + response.failed_count(),
+            )
+        })
+}
+
 /// SMS sending status information
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SendStatus {
@@ -199,9 +776,15 @@ pub struct SendStatus {
     #[serde(rename = "Fee")]
     pub fee: i32,
 
-    /// User session context
-    #[serde(rename = "SessionContext", default)]
-    pub session_context: String,
+    /// User session context echoed from the request
+    /// `None` when the field is absent from the response, `Some("")` when it
+    /// was returned empty
+    #[serde(
+        rename = "SessionContext",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub session_context: Option<String>,
 
     /// SMS delivery status code
     #[serde(rename = "Code")]
@@ -222,6 +805,11 @@ impl SendStatus {
         self.code == "Ok"
     }
 
+    /// Get the typed status code
+    pub fn status_code(&self) -> SendStatusCode {
+        SendStatusCode::parse(&self.code)
+    }
+
     /// Get a human-readable status description
     pub fn get_status_description(&self) -> &str {
         match self.code.as_str() {
@@ -237,6 +825,101 @@ impl SendStatus {
     }
 }
 
+/// Typed per-number delivery status code from `SendStatus::code`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SendStatusCode {
+    /// `Ok`
+    Ok,
+    /// `InvalidParameterValue.IncorrectPhoneNumber`
+    IncorrectPhoneNumber,
+    /// `FailedOperation.SignatureIncorrectOrUnapproved`
+    SignatureIncorrectOrUnapproved,
+    /// `FailedOperation.TemplateIncorrectOrUnapproved`
+    TemplateIncorrectOrUnapproved,
+    /// `FailedOperation.InsufficientBalanceInSmsPackage`
+    InsufficientBalance,
+    /// `LimitExceeded.PhoneNumberCountLimit`
+    PhoneNumberCountLimit,
+    /// `LimitExceeded.DeliveryFrequencyLimit`
+    DeliveryFrequencyLimit,
+    /// Any code not listed above, kept verbatim
+    Other(String),
+}
+
+impl SendStatusCode {
+    /// Parse a status code string
+    pub fn parse(code: &str) -> Self {
+        match code {
+            "Ok" => Self::Ok,
+            "InvalidParameterValue.IncorrectPhoneNumber" => Self::IncorrectPhoneNumber,
+            "FailedOperation.SignatureIncorrectOrUnapproved" => {
+                Self::SignatureIncorrectOrUnapproved
+            }
+            "FailedOperation.TemplateIncorrectOrUnapproved" => Self::TemplateIncorrectOrUnapproved,
+            "FailedOperation.InsufficientBalanceInSmsPackage" => Self::InsufficientBalance,
+            "LimitExceeded.PhoneNumberCountLimit" => Self::PhoneNumberCountLimit,
+            "LimitExceeded.DeliveryFrequencyLimit" => Self::DeliveryFrequencyLimit,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Get the status code string as returned by the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ok => "Ok",
+            Self::IncorrectPhoneNumber => "InvalidParameterValue.IncorrectPhoneNumber",
+            Self::SignatureIncorrectOrUnapproved => {
+                "FailedOperation.SignatureIncorrectOrUnapproved"
+            }
+            Self::TemplateIncorrectOrUnapproved => "FailedOperation.TemplateIncorrectOrUnapproved",
+            Self::InsufficientBalance => "FailedOperation.InsufficientBalanceInSmsPackage",
+            Self::PhoneNumberCountLimit => "LimitExceeded.PhoneNumberCountLimit",
+            Self::DeliveryFrequencyLimit => "LimitExceeded.DeliveryFrequencyLimit",
+            Self::Other(code) => code,
+        }
+    }
+
+    /// Get the coarse category of the code, the part before the first `.`
+    ///
+    /// E.g. `FailedOperation` for `FailedOperation.SignatureIncorrectOrUnapproved`,
+    /// for routing alerts by kind of failure rather than by exact code.
+    pub fn category(&self) -> &str {
+        let code = self.as_str();
+        code.split_once('.').map_or(code, |(category, _)| category)
+    }
+
+    /// Check if sending to the same number again later may succeed
+    ///
+    /// True for throttling and internal errors; an invalid number or an
+    /// unapproved sign or template fails the same way on every attempt.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::DeliveryFrequencyLimit => true,
+            Self::Other(code) => code.starts_with("InternalError"),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for SendStatusCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A number that failed to send, with its typed status code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Phone number
+    pub phone: String,
+    /// Delivery status code
+    pub code: SendStatusCode,
+    /// Delivery status message
+    pub message: String,
+    /// Serial number returned by the SMS sending API
+    pub serial_no: String,
+}
+
 /// Response structure for sending SMS
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SendSmsResponse {
@@ -246,7 +929,7 @@ pub struct SendSmsResponse {
 
     /// Unique request ID
     #[serde(rename = "RequestId")]
-    pub request_id: String,
+    pub request_id: RequestId,
 }
 
 impl SendSmsResponse {
@@ -282,6 +965,71 @@ impl SendSmsResponse {
             .collect()
     }
 
+    /// Get every failed number with its typed status code
+    pub fn failures(&self) -> Vec<Failure> {
+        self.send_status_set
+            .iter()
+            .filter(|status| !status.is_success())
+            .map(|status| Failure {
+                phone: status.phone_number.clone(),
+                code: status.status_code(),
+                message: status.message.clone(),
+                serial_no: status.serial_no.clone(),
+            })
+            .collect()
+    }
+
+    /// Get the distinct status codes of the numbers that failed
+    ///
+    /// Use [`SendStatusCode::category`] on the parsed codes for coarser
+    /// routing, e.g. paging on `FailedOperation` but not `InvalidParameterValue`.
+    pub fn error_codes(&self) -> HashSet<String> {
+        self.send_status_set
+            .iter()
+            .filter(|status| !status.is_success())
+            .map(|status| status.code.clone())
+            .collect()
+    }
+
+    /// Get the phone numbers whose status code equals `code`
+    pub fn numbers_with_code(&self, code: &str) -> Vec<String> {
+        self.send_status_set
+            .iter()
+            .filter(|status| status.code == code)
+            .map(|status| status.phone_number.clone())
+            .collect()
+    }
+
+    /// Get the phone numbers with the given typed status code
+    ///
+    /// Useful for targeted retries, e.g. of every throttled number.
+    pub fn numbers_with(&self, code: SendStatusCode) -> Vec<String> {
+        self.numbers_with_code(code.as_str())
+    }
+
+    /// Return the response only if every message was sent successfully
+    ///
+    /// Otherwise returns an error listing each failed number with its code, so
+    /// pipelines that require full delivery can use `?`.
+    pub fn all_ok_or_err(self) -> Result<Self, TencentCloudError> {
+        if self.is_all_success() {
+            return Ok(self);
+        }
+
+        let failures: Vec<String> = self
+            .failures()
+            .iter()
+            .map(|failure| format!("{}: {} ({})", failure.phone, failure.code, failure.message))
+            .collect();
+        Err(TencentCloudError::other(format!(
+            "{} of {} messages failed (Request ID: {}): {}",
+            failures.len(),
+            self.send_status_set.len(),
+            self.request_id,
+            failures.join("; ")
+        )))
+    }
+
     /// Get successful phone numbers
     pub fn get_successful_numbers(&self) -> Vec<String> {
         self.send_status_set
@@ -307,40 +1055,220 @@ impl SendSmsResponse {
             .find(|status| status.phone_number == phone_number)
     }
 
+    /// Get statuses aligned with the order of the input phone numbers
+    ///
+    /// The API does not guarantee that `SendStatusSet` follows the order of
+    /// `PhoneNumberSet`. Each entry of the result is the status for the phone
+    /// number at the same position of `input`, or `None` if the response has
+    /// no status for it. A number listed twice is matched to two different
+    /// statuses when the response contains two.
+    pub fn ordered_by(&self, input: &[String]) -> Vec<Option<&SendStatus>> {
+        let mut used = vec![false; self.send_status_set.len()];
+        input
+            .iter()
+            .map(|phone| {
+                let index = self
+                    .send_status_set
+                    .iter()
+                    .enumerate()
+                    .position(|(i, status)| !used[i] && &status.phone_number == phone)?;
+                used[index] = true;
+                Some(&self.send_status_set[index])
+            })
+            .collect()
+    }
+
     /// Get total fee for all sent messages
     pub fn get_total_fee(&self) -> i32 {
         self.send_status_set.iter().map(|status| status.fee).sum()
     }
 
-    /// Convert to JSON string
-    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string(self)
+    /// Check that `other` reports the same outcome for every number
+    ///
+    /// Compares each number's status code and serial number regardless of
+    /// order, ignoring the request ID, messages and fees. Use it to confirm
+    /// that a retried send with the same idempotency key was not sent twice.
+    pub fn is_consistent_with(&self, other: &SendSmsResponse) -> bool {
+        fn outcomes(response: &SendSmsResponse) -> Vec<(&str, &str, &str)> {
+            let mut outcomes: Vec<(&str, &str, &str)> = response
+                .send_status_set
+                .iter()
+                .map(|status| {
+                    (
+                        status.phone_number.as_str(),
+                        status.code.as_str(),
+                        status.serial_no.as_str(),
+                    )
+                })
+                .collect();
+            outcomes.sort_unstable();
+            outcomes
+        }
+        outcomes(self) == outcomes(other)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Count statuses per ISO country code
+    ///
+    /// Statuses with an empty `IsoCode` are counted under `"Unknown"`.
+    pub fn country_distribution(&self) -> HashMap<String, usize> {
+        let mut distribution = HashMap::new();
+        for status in &self.send_status_set {
+            let iso_code = if status.iso_code.is_empty() {
+                "Unknown"
+            } else {
+                status.iso_code.as_str()
+            };
+            *distribution.entry(iso_code.to_string()).or_insert(0) += 1;
+        }
+        distribution
+    }
 
-    #[test]
-    fn test_send_sms_request_creation() {
-        let request = SendSmsRequest::new(
-            vec!["+8613800000000".to_string()],
-            "1400000000",
-            "123456",
-            "TestSignature",
-            vec!["123456".to_string()],
-        );
+    /// Get the `n` most frequent ISO country codes with their counts
+    ///
+    /// Sorted by count, most frequent first; ties are ordered by code.
+    pub fn top_countries(&self, n: usize) -> Vec<(String, usize)> {
+        let mut countries: Vec<(String, usize)> = self.country_distribution().into_iter().collect();
+        countries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        countries.truncate(n);
+        countries
+    }
 
-        assert_eq!(request.phone_number_set, vec!["+8613800000000"]);
-        assert_eq!(request.sms_sdk_app_id, "1400000000");
-        assert_eq!(request.template_id, "123456");
-        assert_eq!(request.sign_name, Some("TestSignature".to_string()));
-        assert_eq!(request.template_param_set, Some(vec!["123456".to_string()]));
+    /// Get the distinct phone numbers that were billed (fee above zero)
+    ///
+    /// A long message split into several segments is billed once per
+    /// segment but appears here once.
+    pub fn billed_numbers(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.send_status_set
+            .iter()
+            .filter(|status| status.fee > 0 && seen.insert(status.phone_number.as_str()))
+            .map(|status| status.phone_number.clone())
+            .collect()
     }
 
-    #[test]
-    fn test_send_sms_request_international() {
+    /// Count the distinct phone numbers that were billed
+    pub fn billed_recipient_count(&self) -> usize {
+        self.billed_numbers().len()
+    }
+
+    /// Convert to JSON string
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a response from JSON, as produced by [`SendSmsResponse::to_json_string`]
+    ///
+    /// Accepts both the bare response and the API's `{"Response": {...}}`
+    /// envelope, so archived raw bodies can be reloaded directly.
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(inner) = value.get_mut("Response") {
+            value = inner.take();
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Parse newline-delimited responses, skipping blank lines
+    pub fn from_ndjson(ndjson: &str) -> Result<Vec<Self>, serde_json::Error> {
+        ndjson
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Self::from_json_str)
+            .collect()
+    }
+
+    /// Export the send statuses as CSV
+    ///
+    /// Emits a header row plus one row per status, with fields quoted per
+    /// RFC 4180 and rows terminated by CRLF. A missing session context is an
+    /// empty field.
+    pub fn to_csv(&self) -> String {
+        Self::to_csv_all(std::slice::from_ref(self))
+    }
+
+    /// Export the send statuses of several responses as one CSV document
+    ///
+    /// Same format as [`SendSmsResponse::to_csv`], with a single header row.
+    pub fn to_csv_all(responses: &[SendSmsResponse]) -> String {
+        let mut csv = csv_row(&CSV_HEADER);
+        for status in responses.iter().flat_map(|r| &r.send_status_set) {
+            let fee = status.fee.to_string();
+            csv.push_str(&csv_row(&[
+                &status.phone_number,
+                &status.code,
+                &status.message,
+                &fee,
+                &status.serial_no,
+                &status.iso_code,
+                status.session_context.as_deref().unwrap_or_default(),
+            ]));
+        }
+        csv
+    }
+}
+
+/// Column names of [`SendSmsResponse::to_csv`]
+const CSV_HEADER: [&str; 7] = [
+    "phone_number",
+    "code",
+    "message",
+    "fee",
+    "serial_no",
+    "iso_code",
+    "session_context",
+];
+
+/// Format one CSV row, quoting fields that contain separators or quotes
+fn csv_row(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a domestic send status with empty serial number and message
+    fn status(phone: &str, code: &str, fee: i32) -> SendStatus {
+        SendStatus {
+            serial_no: String::new(),
+            phone_number: phone.to_string(),
+            fee,
+            session_context: None,
+            code: code.to_string(),
+            message: String::new(),
+            iso_code: "CN".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_send_sms_request_creation() {
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+
+        assert_eq!(request.phone_number_set, vec!["+8613800000000"]);
+        assert_eq!(request.sms_sdk_app_id, "1400000000");
+        assert_eq!(request.template_id, "123456");
+        assert_eq!(request.sign_name, Some("TestSignature".to_string()));
+        assert_eq!(request.template_param_set, Some(vec!["123456".to_string()]));
+    }
+
+    #[test]
+    fn test_send_sms_request_international() {
         let request = SendSmsRequest::new_international(
             vec!["+1234567890".to_string()],
             "1400000000",
@@ -399,13 +1327,84 @@ mod tests {
         assert!(request.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_nation_diversity() {
+        let request = SendSmsRequest::new_international(
+            vec![
+                "+8613800000000".to_string(),
+                "+12025550123".to_string(),
+                "+447911123456".to_string(),
+                "+819012345678".to_string(),
+            ],
+            "1400000000",
+            "123456",
+            vec!["123456".to_string()],
+        );
+
+        assert!(request.validate_nation_diversity(4).is_ok());
+        let error = request.validate_nation_diversity(3).unwrap_err();
+        assert!(error.contains("4 nation codes"));
+    }
+
+    #[test]
+    fn test_validate_body_size() {
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["x".repeat(MAX_REQUEST_BODY_SIZE)],
+        );
+
+        let error = request.validate().unwrap_err();
+        assert!(error.contains("exceeding the limit"));
+
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        assert!(request.validate_body_size(1024).is_ok());
+        assert!(request.validate_body_size(16).is_err());
+    }
+
+    #[test]
+    fn test_sender_id_with_extend_code() {
+        let mut request = SendSmsRequest::new_international(
+            vec!["+12025550123".to_string()],
+            "1400000000",
+            "123456",
+            vec!["123456".to_string()],
+        );
+        request.set_sender_id("SENDER");
+        assert!(request.validate().is_ok());
+
+        request.set_extend_code("01");
+        assert_eq!(
+            request.validate().unwrap_err(),
+            "Extend code cannot be used together with a sender ID"
+        );
+        assert!(matches!(
+            SendSmsRequest::builder()
+                .phone_number("+12025550123")
+                .sms_sdk_app_id("1400000000")
+                .template_id("123456")
+                .sender_id("SENDER")
+                .extend_code("01")
+                .build(),
+            Err(crate::error::TencentCloudError::Parameter(_))
+        ));
+    }
+
     #[test]
     fn test_send_status() {
         let status = SendStatus {
             serial_no: "12345".to_string(),
             phone_number: "+8613800000000".to_string(),
             fee: 1,
-            session_context: "test".to_string(),
+            session_context: Some("test".to_string()),
             code: "Ok".to_string(),
             message: "Success".to_string(),
             iso_code: "CN".to_string(),
@@ -418,7 +1417,7 @@ mod tests {
             serial_no: "12345".to_string(),
             phone_number: "+8613800000000".to_string(),
             fee: 0,
-            session_context: "test".to_string(),
+            session_context: Some("test".to_string()),
             code: "InvalidParameterValue.IncorrectPhoneNumber".to_string(),
             message: "Invalid phone number".to_string(),
             iso_code: "CN".to_string(),
@@ -439,7 +1438,7 @@ mod tests {
                     serial_no: "12345".to_string(),
                     phone_number: "+8613800000000".to_string(),
                     fee: 1,
-                    session_context: "test".to_string(),
+                    session_context: Some("test".to_string()),
                     code: "Ok".to_string(),
                     message: "Success".to_string(),
                     iso_code: "CN".to_string(),
@@ -448,13 +1447,13 @@ mod tests {
                     serial_no: "12346".to_string(),
                     phone_number: "+8613800000001".to_string(),
                     fee: 0,
-                    session_context: "test".to_string(),
+                    session_context: Some("test".to_string()),
                     code: "InvalidParameterValue.IncorrectPhoneNumber".to_string(),
                     message: "Invalid phone number".to_string(),
                     iso_code: "CN".to_string(),
                 },
             ],
-            request_id: "test-request-id".to_string(),
+            request_id: "test-request-id".into(),
         };
 
         assert!(!response.is_all_success());
@@ -472,4 +1471,766 @@ mod tests {
         assert!(response.check_phone_success("+8613800000000"));
         assert!(!response.check_phone_success("+8613800000001"));
     }
+
+    #[test]
+    fn test_send_sms_response_failures() {
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                SendStatus {
+                    serial_no: "1".to_string(),
+                    message: "send success".to_string(),
+                    ..status("+8613800000000", "Ok", 0)
+                },
+                SendStatus {
+                    serial_no: "2".to_string(),
+                    message: "too frequent".to_string(),
+                    ..status("+8613800000001", "LimitExceeded.DeliveryFrequencyLimit", 0)
+                },
+                SendStatus {
+                    serial_no: "3".to_string(),
+                    message: "bad number".to_string(),
+                    ..status(
+                        "+8613800000002",
+                        "InvalidParameterValue.IncorrectPhoneNumber",
+                        0,
+                    )
+                },
+                SendStatus {
+                    serial_no: "4".to_string(),
+                    message: "unknown".to_string(),
+                    ..status("+8613800000003", "FailedOperation.Unknown", 0)
+                },
+            ],
+            request_id: "test-request-id".into(),
+        };
+
+        let failures = response.failures();
+        assert_eq!(
+            failures,
+            vec![
+                Failure {
+                    phone: "+8613800000001".to_string(),
+                    code: SendStatusCode::DeliveryFrequencyLimit,
+                    message: "too frequent".to_string(),
+                    serial_no: "2".to_string(),
+                },
+                Failure {
+                    phone: "+8613800000002".to_string(),
+                    code: SendStatusCode::IncorrectPhoneNumber,
+                    message: "bad number".to_string(),
+                    serial_no: "3".to_string(),
+                },
+                Failure {
+                    phone: "+8613800000003".to_string(),
+                    code: SendStatusCode::Other("FailedOperation.Unknown".to_string()),
+                    message: "unknown".to_string(),
+                    serial_no: "4".to_string(),
+                },
+            ]
+        );
+        assert!(failures[0].code.is_retryable());
+        assert!(!failures[1].code.is_retryable());
+        assert_eq!(failures[2].code.as_str(), "FailedOperation.Unknown");
+    }
+
+    #[test]
+    fn test_drop_invalid() {
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string(), "not-a-number".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        assert!(request.validate().is_err());
+
+        let dropped = request.drop_invalid();
+        assert_eq!(dropped, vec!["not-a-number"]);
+        assert_eq!(request.phone_number_set, vec!["+8613800000000"]);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_ordered_by() {
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                SendStatus {
+                    serial_no: "3".to_string(),
+                    ..status("+8613800000003", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "1".to_string(),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "2".to_string(),
+                    ..status("+8613800000002", "Ok", 1)
+                },
+            ],
+            request_id: "test-request-id".into(),
+        };
+        let input = vec![
+            "+8613800000001".to_string(),
+            "+8613800000002".to_string(),
+            "+8613800000004".to_string(),
+            "+8613800000003".to_string(),
+        ];
+
+        let serials: Vec<Option<&str>> = response
+            .ordered_by(&input)
+            .into_iter()
+            .map(|status| status.map(|s| s.serial_no.as_str()))
+            .collect();
+        assert_eq!(serials, vec![Some("1"), Some("2"), None, Some("3")]);
+    }
+
+    #[test]
+    fn test_validate_batch() {
+        let request = |phone: &str, template_id: &str| {
+            SendSmsRequest::new(
+                vec![phone.to_string()],
+                "1400000000",
+                template_id,
+                "TestSignature",
+                vec!["123456".to_string()],
+            )
+        };
+        let requests = vec![
+            request("+8613800000000", "123456"),
+            request("+8613800000001", ""),
+            request("+8613800000002", "123456"),
+            request("bad", "123456"),
+        ];
+
+        let errors = validate_batch(&requests).unwrap_err();
+        let reported: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|(index, error)| (*index, error.field.as_str()))
+            .collect();
+        assert_eq!(reported, vec![(1, "template_id"), (3, "phone_number_set")]);
+
+        assert!(validate_batch(&requests[..1]).is_ok());
+        assert!(validate_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_all_ok_or_err() {
+        let response = SendSmsResponse {
+            send_status_set: vec![status("+8613800000000", "Ok", 1)],
+            request_id: "test-request-id".into(),
+        };
+        assert!(response.all_ok_or_err().is_ok());
+
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                status("+8613800000000", "Ok", 1),
+                status(
+                    "+8613800000001",
+                    "InvalidParameterValue.IncorrectPhoneNumber",
+                    1,
+                ),
+            ],
+            request_id: "test-request-id".into(),
+        };
+        let error = response.all_ok_or_err().unwrap_err().to_string();
+        assert!(error.contains("1 of 2 messages failed"));
+        assert!(error.contains("+8613800000001: InvalidParameterValue.IncorrectPhoneNumber"));
+        assert!(!error.contains("+8613800000000"));
+    }
+
+    #[test]
+    fn test_validate_domestic_sign_name() {
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "",
+            vec!["123456".to_string()],
+        );
+        let error = request.check().unwrap_err();
+        assert_eq!(error.field, "sign_name");
+        assert!(error.message.contains("Sign name is empty"));
+
+        // Whitespace-only signatures are empty too
+        request.set_sign_name("  ");
+        assert!(request.validate().is_err());
+
+        // So are the other mainland China formats
+        request.phone_number_set = vec!["8613800000000".to_string()];
+        assert!(request.validate().is_err());
+
+        // International numbers do not need a signature
+        request.phone_number_set = vec!["+12025550123".to_string()];
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_to_redacted_json() {
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string(), "+12025550123".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["987654".to_string(), "5".to_string()],
+        );
+        request.set_session_context("user-42");
+
+        let json = request.to_redacted_json();
+        assert!(json.contains(r#""PhoneNumberCount":2"#));
+        assert!(json.contains(r#""PhoneNumberSet":["+86****","+1****"]"#));
+        assert!(json.contains(r#""TemplateParamSet":["REDACTED","REDACTED"]"#));
+        assert!(json.contains(r#""TemplateId":"123456""#));
+        for secret in ["13800000000", "2025550123", "987654", "user-42"] {
+            assert!(!json.contains(secret), "{} leaked", secret);
+        }
+    }
+
+    #[test]
+    fn test_send_status_session_context() {
+        let json = r#"{"SerialNo":"1","PhoneNumber":"+8613800000000","Fee":1,"Code":"Ok","Message":"send success","IsoCode":"CN""#;
+
+        let absent: SendStatus = serde_json::from_str(&format!("{}}}", json)).unwrap();
+        assert_eq!(absent.session_context, None);
+
+        let empty: SendStatus =
+            serde_json::from_str(&format!(r#"{},"SessionContext":""}}"#, json)).unwrap();
+        assert_eq!(empty.session_context, Some(String::new()));
+
+        let present: SendStatus =
+            serde_json::from_str(&format!(r#"{},"SessionContext":"order-42"}}"#, json)).unwrap();
+        assert_eq!(present.session_context.as_deref(), Some("order-42"));
+    }
+
+    #[test]
+    fn test_total_fee_and_aggregate_counts() {
+        let responses = vec![
+            SendSmsResponse {
+                send_status_set: vec![
+                    status("+8613800000000", "Ok", 1),
+                    status("+8613800000000", "Ok", 2),
+                ],
+                request_id: "first".into(),
+            },
+            SendSmsResponse {
+                send_status_set: vec![
+                    status("+8613800000000", "Ok", 1),
+                    status("+8613800000000", "LimitExceeded.DeliveryFrequencyLimit", 0),
+                ],
+                request_id: "second".into(),
+            },
+        ];
+
+        assert_eq!(total_fee(&responses), 4);
+        assert_eq!(aggregate_counts(&responses), (3, 1));
+        assert_eq!(total_fee(&[]), 0);
+        assert_eq!(aggregate_counts(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_validate_alphanumeric_template_id() {
+        let request = SendSmsRequest::new_international(
+            vec!["+12025550123".to_string()],
+            "1400000000",
+            "INTL_OTP_v2",
+            vec!["123456".to_string()],
+        );
+        assert!(request.validate().is_ok());
+        assert!(serde_json::to_string(&request)
+            .unwrap()
+            .contains(r#""TemplateId":"INTL_OTP_v2""#));
+    }
+
+    #[test]
+    fn test_add_recipients() {
+        let mut request = SendSmsRequest::new(
+            Vec::new(),
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        request
+            .add_recipient("+8613800000000")
+            .add_recipients(["+8613800000001", "+8613800000002"]);
+        assert_eq!(request.phone_number_set.len(), 3);
+        assert!(request.validate().is_ok());
+
+        request.add_recipients((3..=200).map(|i| format!("+8613800{:06}", i)));
+        assert_eq!(request.phone_number_set.len(), 201);
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_numbers_with_code() {
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                status("+8613800000000", "LimitExceeded.DeliveryFrequencyLimit", 0),
+                status("+8613800000001", "Ok", 0),
+                status("+8613800000002", "LimitExceeded.DeliveryFrequencyLimit", 0),
+                status(
+                    "+8613800000003",
+                    "InvalidParameterValue.IncorrectPhoneNumber",
+                    0,
+                ),
+            ],
+            request_id: "test-request-id".into(),
+        };
+
+        let throttled = vec!["+8613800000000", "+8613800000002"];
+        assert_eq!(
+            response.numbers_with_code("LimitExceeded.DeliveryFrequencyLimit"),
+            throttled
+        );
+        assert_eq!(
+            response.numbers_with(SendStatusCode::DeliveryFrequencyLimit),
+            throttled
+        );
+        assert_eq!(
+            response.numbers_with(SendStatusCode::Ok),
+            vec!["+8613800000001"]
+        );
+        assert!(response
+            .numbers_with_code("FailedOperation.Unknown")
+            .is_empty());
+
+        let codes = response.error_codes();
+        assert_eq!(
+            codes,
+            HashSet::from([
+                "LimitExceeded.DeliveryFrequencyLimit".to_string(),
+                "InvalidParameterValue.IncorrectPhoneNumber".to_string(),
+            ])
+        );
+        let categories: HashSet<String> = codes
+            .iter()
+            .map(|code| SendStatusCode::parse(code).category().to_string())
+            .collect();
+        assert_eq!(
+            categories,
+            HashSet::from([
+                "LimitExceeded".to_string(),
+                "InvalidParameterValue".to_string()
+            ])
+        );
+        assert_eq!(SendStatusCode::Ok.category(), "Ok");
+    }
+
+    #[test]
+    fn test_request_id_deserializes_transparently() {
+        let json = r#"{"SendStatusSet":[],"RequestId":"a0b1c2d3-e4f5"}"#;
+        let response: SendSmsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.request_id, RequestId::new("a0b1c2d3-e4f5"));
+        assert_eq!(response.request_id.as_str(), "a0b1c2d3-e4f5");
+        assert_eq!(response.request_id.to_string(), "a0b1c2d3-e4f5");
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let request = |phones: &[&str]| {
+            SendSmsRequest::new(
+                phones.iter().map(|p| p.to_string()).collect(),
+                "1400000000",
+                "123456",
+                "TestSignature",
+                vec!["1234".to_string()],
+            )
+        };
+        let mut first = request(&["+8613800000000", "+8613800000001"]);
+        let second = request(&["+8613800000001", "+8613800000000"]);
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(first.fingerprint().len(), 64);
+
+        first.set_session_context("retry-1");
+        assert_eq!(first.fingerprint(), second.fingerprint());
+
+        let mut changed = second.clone();
+        changed.template_param_set = Some(vec!["5678".to_string()]);
+        assert_ne!(changed.fingerprint(), second.fingerprint());
+        assert_ne!(
+            request(&["+8613800000000"]).fingerprint(),
+            second.fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let first = SendSmsResponse {
+            send_status_set: vec![
+                SendStatus {
+                    serial_no: "2019:123".to_string(),
+                    message: "send success".to_string(),
+                    session_context: None,
+                    ..status("+8613800000000", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "2019:123".to_string(),
+                    message: "queued, retrying".to_string(),
+                    session_context: Some(r#"say "hi""#.to_string()),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+            ],
+            request_id: "first".into(),
+        };
+        let second = SendSmsResponse {
+            send_status_set: vec![SendStatus {
+                serial_no: "2019:123".to_string(),
+                message: "send success".to_string(),
+                session_context: None,
+                ..status("+8613800000002", "Ok", 1)
+            }],
+            request_id: "second".into(),
+        };
+
+        let csv = first.to_csv();
+        let rows: Vec<&str> = csv.split_terminator("\r\n").collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            "phone_number,code,message,fee,serial_no,iso_code,session_context"
+        );
+        assert_eq!(rows[1], "+8613800000000,Ok,send success,1,2019:123,CN,");
+        assert_eq!(
+            rows[2],
+            r#"+8613800000001,Ok,"queued, retrying",1,2019:123,CN,"say ""hi""""#
+        );
+
+        let csv = SendSmsResponse::to_csv_all(&[first, second]);
+        assert_eq!(csv.split_terminator("\r\n").count(), 4);
+        assert!(csv.ends_with("+8613800000002,Ok,send success,1,2019:123,CN,\r\n"));
+    }
+
+    #[test]
+    fn test_from_json_str() {
+        let response = SendSmsResponse {
+            send_status_set: vec![SendStatus {
+                serial_no: "2019:123".to_string(),
+                phone_number: "+8613800000000".to_string(),
+                fee: 1,
+                session_context: None,
+                code: "Ok".to_string(),
+                message: "send success".to_string(),
+                iso_code: "CN".to_string(),
+            }],
+            request_id: "test-request-id".into(),
+        };
+
+        let json = response.to_json_string().unwrap();
+        let parsed = SendSmsResponse::from_json_str(&json).unwrap();
+        assert_eq!(parsed.to_json_string().unwrap(), json);
+
+        let enveloped = format!(r#"{{"Response":{}}}"#, json);
+        let ndjson = format!("{}\n\n{}\n", json, enveloped);
+        let parsed = SendSmsResponse::from_ndjson(&ndjson).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].request_id, "test-request-id");
+        assert!(SendSmsResponse::from_ndjson("{}\nnot json").is_err());
+    }
+
+    #[test]
+    fn test_check_rendered_length() {
+        let template = "您的验证码是{1}，请于{2}分钟内填写。";
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string(), "5".to_string()],
+        );
+        assert_eq!(
+            render_template(template, request.template_param_set.as_ref().unwrap()),
+            "您的验证码是123456，请于5分钟内填写。"
+        );
+        assert!(request.check_rendered_length(template, 22).is_ok());
+        assert!(request.check_rendered_length(template, 21).is_err());
+
+        request.set_template_param_set(vec!["码".repeat(60), "5".to_string()]);
+        let error = request.check_rendered_length(template, 70).unwrap_err();
+        assert!(error.contains("76 characters"), "{}", error);
+
+        // Unknown placeholders are kept verbatim
+        assert_eq!(render_template("{0}{3}{x", &["a".to_string()]), "{0}{3}{x");
+    }
+
+    #[test]
+    fn test_to_api_explorer_params() {
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string(), "+8613800000001".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["1234".to_string(), "5".to_string()],
+        );
+        let params = request.to_api_explorer_params();
+        let expected = [
+            ("PhoneNumberSet.0", "+8613800000000"),
+            ("PhoneNumberSet.1", "+8613800000001"),
+            ("SmsSdkAppId", "1400000000"),
+            ("TemplateId", "123456"),
+            ("SignName", "TestSignature"),
+            ("TemplateParamSet.0", "1234"),
+            ("TemplateParamSet.1", "5"),
+        ];
+        assert_eq!(params.len(), expected.len());
+        for (key, value) in expected {
+            assert!(
+                params.contains(&(key.to_string(), value.to_string())),
+                "missing {}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_against_template() {
+        let template = "您的验证码是{1}，请于{2}分钟内填写。{1}";
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        let error = request.check_against_template(template).unwrap_err();
+        assert!(
+            error.contains("2 placeholders but 1 parameters"),
+            "{}",
+            error
+        );
+
+        request.set_template_param_set(vec!["123456".to_string(), "5".to_string()]);
+        assert!(request.check_against_template(template).is_ok());
+        assert!(request.check_against_template("{0}{x} no params").is_err());
+
+        // Two placeholders for two parameters, but {2} is never used
+        let error = request
+            .check_against_template("验证码{1}，{3}分钟内有效")
+            .unwrap_err();
+        assert!(error.contains("{2} is missing"), "{}", error);
+    }
+
+    #[test]
+    fn test_billed_recipients() {
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                status("+8613800000000", "Ok", 2),
+                status("+8613800000001", "Ok", 1),
+                status("+8613800000002", "LimitExceeded.DeliveryFrequencyLimit", 0),
+            ],
+            request_id: "test-request-id".into(),
+        };
+
+        assert_eq!(response.get_total_fee(), 3);
+        assert_eq!(response.billed_recipient_count(), 2);
+        assert_eq!(
+            response.billed_numbers(),
+            vec!["+8613800000000", "+8613800000001"]
+        );
+    }
+
+    #[test]
+    fn test_is_consistent_with() {
+        let response = |request_id: &str, statuses: Vec<SendStatus>| SendSmsResponse {
+            send_status_set: statuses,
+            request_id: request_id.into(),
+        };
+        let first = response(
+            "request-1",
+            vec![
+                SendStatus {
+                    serial_no: "2019:1".to_string(),
+                    ..status("+8613800000000", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "2019:2".to_string(),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+            ],
+        );
+        let replay = response(
+            "request-2",
+            vec![
+                SendStatus {
+                    serial_no: "2019:2".to_string(),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "2019:1".to_string(),
+                    ..status("+8613800000000", "Ok", 1)
+                },
+            ],
+        );
+        let resent = response(
+            "request-3",
+            vec![
+                SendStatus {
+                    serial_no: "2019:1".to_string(),
+                    ..status("+8613800000000", "Ok", 1)
+                },
+                SendStatus {
+                    serial_no: "2019:3".to_string(),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+            ],
+        );
+
+        assert!(first.is_consistent_with(&replay));
+        assert!(!first.is_consistent_with(&resent));
+    }
+
+    #[test]
+    fn test_validate_param_lengths() {
+        let request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string(), "1234567890123".to_string()],
+        );
+        assert!(request.validate_param_lengths(13).is_ok());
+        let error = request.validate_param_lengths(12).unwrap_err();
+        assert!(error.starts_with("Template parameter 1 "), "{}", error);
+        assert!(error.contains("\"1234567890123\""), "{}", error);
+    }
+
+    #[test]
+    fn test_validate_sender_id() {
+        let mut request = SendSmsRequest::new_international(
+            vec!["+12025550123".to_string(), "+971501234567".to_string()],
+            "1400000000",
+            "123456",
+            vec!["123456".to_string()],
+        );
+        assert!(request.validate().is_ok());
+        let error = request.validate_sender_id().unwrap_err();
+        assert!(error.contains("+971501234567"), "{}", error);
+
+        request.set_sender_id("ACME");
+        assert!(request.validate_sender_id().is_ok());
+    }
+
+    #[test]
+    fn test_cross_format_duplicates() {
+        let mut request = SendSmsRequest::new(
+            vec![
+                "13800000000".to_string(),
+                "+8613800000001".to_string(),
+                "+8613800000000".to_string(),
+                "+8613800000001".to_string(),
+            ],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        assert_eq!(
+            request.cross_format_duplicates("86"),
+            vec![("13800000000".to_string(), "+8613800000000".to_string())]
+        );
+
+        let errors = request.validate_all("86").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "phone_number_set");
+        assert!(
+            errors[0].message.contains("+8613800000000"),
+            "{}",
+            errors[0]
+        );
+
+        request.phone_number_set.remove(2);
+        assert!(request.validate_all("86").is_ok());
+    }
+
+    #[test]
+    fn test_short_code_sender_id_is_not_a_recipient() {
+        let mut request = SendSmsRequest::new_international(
+            vec!["+971501234567".to_string(), "+12025550123".to_string()],
+            "1400000000",
+            "123456",
+            vec!["123456".to_string()],
+        );
+        request.set_sender_id("12345");
+        assert!(request.validate().is_ok());
+        assert!(request.validate_sender_id().is_ok());
+
+        // The same value as a recipient is rejected
+        request.add_recipient("12345");
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_to_json_with_nulls() {
+        let mut request = SendSmsRequest::new(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            vec!["123456".to_string()],
+        );
+        request.set_session_context("ctx");
+
+        let skipped = serde_json::to_value(&request).unwrap();
+        assert!(skipped.get("ExtendCode").is_none());
+        assert!(skipped.get("SenderId").is_none());
+
+        let nulls = request.to_json_with_nulls();
+        assert_eq!(nulls["ExtendCode"], serde_json::Value::Null);
+        assert_eq!(nulls["SenderId"], serde_json::Value::Null);
+        assert_eq!(nulls["SessionContext"], "ctx");
+        assert_eq!(nulls["SignName"], "TestSignature");
+        assert_eq!(nulls["PhoneNumberSet"], skipped["PhoneNumberSet"]);
+    }
+
+    #[test]
+    fn test_country_distribution() {
+        let response = SendSmsResponse {
+            send_status_set: vec![
+                SendStatus {
+                    iso_code: "CN".to_string(),
+                    ..status("+8613800000000", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "CN".to_string(),
+                    ..status("+8613800000001", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "CN".to_string(),
+                    ..status("+8613800000002", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "US".to_string(),
+                    ..status("+12025550123", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "GB".to_string(),
+                    ..status("+447911123456", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "GB".to_string(),
+                    ..status("+447911123457", "Ok", 1)
+                },
+                SendStatus {
+                    iso_code: "".to_string(),
+                    ..status("+0000", "Ok", 1)
+                },
+            ],
+            request_id: "test-request-id".into(),
+        };
+
+        let distribution = response.country_distribution();
+        assert_eq!(distribution.len(), 4);
+        assert_eq!(distribution["CN"], 3);
+        assert_eq!(distribution["GB"], 2);
+        assert_eq!(distribution["US"], 1);
+        assert_eq!(distribution["Unknown"], 1);
+
+        assert_eq!(
+            response.top_countries(3),
+            vec![
+                ("CN".to_string(), 3),
+                ("GB".to_string(), 2),
+                ("US".to_string(), 1),
+            ]
+        );
+    }
 }
@@ -0,0 +1,221 @@
+//! Typed template parameters
+
+use std::collections::HashMap;
+
+/// Conversion of a typed value into the ordered template parameter list
+///
+/// Template parameters are positional, so keeping their order in one type
+/// avoids mixing them up at each call site. Implement this trait by hand, or
+/// with [`impl_template_params!`](crate::impl_template_params) for structs
+/// whose fields map one-to-one to the parameters.
+pub trait IntoTemplateParams {
+    /// Convert into the template parameter list, in template order
+    fn into_params(self) -> Vec<String>;
+}
+
+impl IntoTemplateParams for Vec<String> {
+    fn into_params(self) -> Vec<String> {
+        self
+    }
+}
+
+impl IntoTemplateParams for Vec<&str> {
+    fn into_params(self) -> Vec<String> {
+        self.into_iter().map(str::to_string).collect()
+    }
+}
+
+impl<const N: usize> IntoTemplateParams for [&str; N] {
+    fn into_params(self) -> Vec<String> {
+        self.iter().map(|param| param.to_string()).collect()
+    }
+}
+
+/// Template parameters supplied by name and ordered by a declared name list
+///
+/// Templates name their variables but the API takes them by position, so the
+/// values are given in any order and placed by the declared order when built.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::NamedParams;
+///
+/// let mut params = NamedParams::new(["code", "expire"]);
+/// params.set("expire", 5).set("code", "123456");
+/// assert_eq!(params.build().unwrap(), vec!["123456", "5"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamedParams {
+    order: Vec<String>,
+    values: HashMap<String, String>,
+}
+
+impl NamedParams {
+    /// Create an empty parameter set for the declared parameter order
+    pub fn new<I, S>(order: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            order: order.into_iter().map(Into::into).collect(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Set the value of a named parameter, replacing any previous value
+    pub fn set<N: Into<String>, V: ToString>(&mut self, name: N, value: V) -> &mut Self {
+        self.values.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Get the declared parameter order
+    pub fn order(&self) -> &[String] {
+        &self.order
+    }
+
+    /// Build the positional parameter list in the declared order
+    ///
+    /// Fails if a declared parameter has no value or a value was set for a
+    /// name that is not declared, which is usually a typo.
+    pub fn build(&self) -> Result<Vec<String>, String> {
+        let missing: Vec<&str> = self
+            .order
+            .iter()
+            .filter(|name| !self.values.contains_key(*name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Missing template parameters: {}",
+                missing.join(", ")
+            ));
+        }
+        let mut unknown: Vec<&str> = self
+            .values
+            .keys()
+            .filter(|name| !self.order.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            return Err(format!(
+                "Unknown template parameters: {}",
+                unknown.join(", ")
+            ));
+        }
+        Ok(self
+            .order
+            .iter()
+            .map(|name| self.values[name].clone())
+            .collect())
+    }
+}
+
+/// Implement [`IntoTemplateParams`](crate::sms::IntoTemplateParams) for a struct
+///
+/// The listed fields become the template parameters in the order given, each
+/// converted with `to_string`.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::impl_template_params;
+/// use tencentcloud_sms_sdk::sms::IntoTemplateParams;
+///
+/// struct OtpParams {
+///     code: String,
+///     ttl_minutes: u32,
+/// }
+///
+/// impl_template_params!(OtpParams { code, ttl_minutes });
+///
+/// let params = OtpParams { code: "123456".to_string(), ttl_minutes: 5 };
+/// assert_eq!(params.into_params(), vec!["123456", "5"]);
+/// ```
+#[macro_export]
+macro_rules! impl_template_params {
+    ($type:ty { $($field:ident),* $(,)? }) => {
+        impl $crate::sms::IntoTemplateParams for $type {
+            fn into_params(self) -> Vec<String> {
+                vec![$(self.$field.to_string()),*]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sms::SendSmsRequest;
+
+    struct OtpParams {
+        code: String,
+        ttl_minutes: u32,
+    }
+
+    crate::impl_template_params!(OtpParams { code, ttl_minutes });
+
+    #[test]
+    fn test_struct_into_params() {
+        let params = OtpParams {
+            code: "123456".to_string(),
+            ttl_minutes: 5,
+        };
+        assert_eq!(params.into_params(), vec!["123456", "5"]);
+    }
+
+    #[test]
+    fn test_send_sms_request_with_params() {
+        let request = SendSmsRequest::with_params(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            OtpParams {
+                code: "654321".to_string(),
+                ttl_minutes: 10,
+            },
+        );
+        assert_eq!(
+            request.template_param_set,
+            Some(vec!["654321".to_string(), "10".to_string()])
+        );
+
+        let request = SendSmsRequest::with_params(
+            vec!["+8613800000000".to_string()],
+            "1400000000",
+            "123456",
+            "TestSignature",
+            ["a", "b"],
+        );
+        assert_eq!(
+            request.template_param_set,
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_named_params_follow_declared_order() {
+        let mut params = NamedParams::new(["code", "expire", "product"]);
+        params
+            .set("product", "Acme")
+            .set("code", "123456")
+            .set("expire", 5);
+        assert_eq!(params.build().unwrap(), vec!["123456", "5", "Acme"]);
+
+        let mut params = NamedParams::new(["code", "expire"]);
+        params.set("code", "123456");
+        let error = params.build().unwrap_err();
+        assert!(error.contains("expire"), "{}", error);
+
+        params.set("expire", 5).set("expiry", 10);
+        let error = params.build().unwrap_err();
+        assert!(
+            error.contains("Unknown template parameters: expiry"),
+            "{}",
+            error
+        );
+    }
+}
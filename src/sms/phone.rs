@@ -0,0 +1,189 @@
+//! Phone number helpers
+
+/// Country calling codes that are two digits long
+///
+/// Apart from the one-digit codes `1` (NANP) and `7` (Russia/Kazakhstan),
+/// every other ITU-T E.164 country code is three digits long.
+const TWO_DIGIT_NATION_CODES: &[&str] = &[
+    "20", "27", "30", "31", "32", "33", "34", "36", "39", "40", "41", "43", "44", "45", "46", "47",
+    "48", "49", "51", "52", "53", "54", "55", "56", "57", "58", "60", "61", "62", "63", "64", "65",
+    "66", "81", "82", "84", "86", "90", "91", "92", "93", "94", "95", "98",
+];
+
+/// Get the country calling code of a phone number
+///
+/// Accepts the formats allowed by [`SendSmsRequest`](crate::sms::SendSmsRequest):
+/// `+[country code][number]`, `00[country code][number]`, and mainland
+/// China numbers without a prefix, either 11 digits or 13 digits starting
+/// with `86` (both resolve to `86`).
+/// Returns `None` if the nation code cannot be determined.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::nation_code;
+///
+/// assert_eq!(nation_code("+8613800000000"), Some("86"));
+/// assert_eq!(nation_code("+12025550123"), Some("1"));
+/// assert_eq!(nation_code("13800000000"), Some("86"));
+/// assert_eq!(nation_code("8613800000000"), Some("86"));
+/// ```
+pub fn nation_code(phone: &str) -> Option<&str> {
+    let digits = if let Some(rest) = phone.strip_prefix('+') {
+        rest
+    } else if let Some(rest) = phone.strip_prefix("00") {
+        rest
+    } else if (phone.len() == 11 && phone.starts_with('1'))
+        || (phone.len() == 13 && phone.starts_with("861"))
+    {
+        return phone.bytes().all(|b| b.is_ascii_digit()).then_some("86");
+    } else {
+        return None;
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let code_len = if digits.starts_with('1') || digits.starts_with('7') {
+        1
+    } else if TWO_DIGIT_NATION_CODES
+        .iter()
+        .any(|code| digits.starts_with(code))
+    {
+        2
+    } else {
+        3
+    };
+
+    digits
+        .get(..code_len)
+        .filter(|code| code.len() < digits.len())
+}
+
+/// Canonicalize a phone number to E.164, `+[country code][number]`
+///
+/// `+` and `00` international prefixes are kept as the country code. A
+/// number without one is taken as national and prefixed with
+/// `default_nation_code`, after dropping a leading `0` trunk prefix. Spaces,
+/// dashes and parentheses are ignored. Returns `None` if anything other
+/// than digits remains.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::to_e164;
+///
+/// assert_eq!(to_e164("13800000000", "86").as_deref(), Some("+8613800000000"));
+/// assert_eq!(to_e164("0086 138-0000-0000", "1").as_deref(), Some("+8613800000000"));
+/// assert_eq!(to_e164("020 7946 0018", "44").as_deref(), Some("+442079460018"));
+/// ```
+pub fn to_e164(phone: &str, default_nation_code: &str) -> Option<String> {
+    let compact: String = phone
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '(' | ')'))
+        .collect();
+    let digits = if let Some(rest) = compact.strip_prefix('+') {
+        rest.to_string()
+    } else if let Some(rest) = compact.strip_prefix("00") {
+        rest.to_string()
+    } else {
+        let national = compact.strip_prefix('0').unwrap_or(&compact);
+        format!("{}{}", default_nation_code, national)
+    };
+    (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| format!("+{}", digits))
+}
+
+/// Country calling codes whose carriers reject international SMS without a SenderId
+///
+/// Kept deliberately short: only destinations where a registered sender ID
+/// is mandatory for every message type. Saudi Arabia, the United Arab
+/// Emirates and Qatar.
+const SENDER_ID_REQUIRED_NATION_CODES: &[&str] = &["966", "971", "974"];
+
+/// Check if messages to a phone number must carry a `SenderId`
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::requires_sender_id;
+///
+/// assert!(requires_sender_id("+971501234567"));
+/// assert!(!requires_sender_id("+12025550123"));
+/// ```
+pub fn requires_sender_id(phone: &str) -> bool {
+    nation_code(phone).is_some_and(|code| SENDER_ID_REQUIRED_NATION_CODES.contains(&code))
+}
+
+/// Check if a phone number is in E.164 format
+///
+/// E.164 numbers are a `+` followed by up to 15 digits, the first of which
+/// (the start of the country code) is not zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::is_e164;
+///
+/// assert!(is_e164("+8613800000000"));
+/// assert!(!is_e164("13800000000"));
+/// ```
+pub fn is_e164(phone: &str) -> bool {
+    match phone.strip_prefix('+') {
+        Some(digits) => {
+            (2..=15).contains(&digits.len())
+                && !digits.starts_with('0')
+                && digits.bytes().all(|b| b.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nation_code() {
+        assert_eq!(nation_code("+8613800000000"), Some("86"));
+        assert_eq!(nation_code("008613800000000"), Some("86"));
+        assert_eq!(nation_code("13800000000"), Some("86"));
+        assert_eq!(nation_code("8613800000000"), Some("86"));
+        assert_eq!(nation_code("+12025550123"), Some("1"));
+        assert_eq!(nation_code("+447911123456"), Some("44"));
+        assert_eq!(nation_code("+85291234567"), Some("852"));
+        assert_eq!(nation_code("+79161234567"), Some("7"));
+    }
+
+    #[test]
+    fn test_nation_code_invalid() {
+        assert_eq!(nation_code(""), None);
+        assert_eq!(nation_code("+"), None);
+        assert_eq!(nation_code("+86"), None);
+        assert_eq!(nation_code("+86abc"), None);
+        assert_eq!(nation_code("12345"), None);
+        assert_eq!(nation_code("86138000000ab"), None);
+    }
+
+    #[test]
+    fn test_requires_sender_id() {
+        assert!(requires_sender_id("+966501234567"));
+        assert!(requires_sender_id("00971501234567"));
+        assert!(!requires_sender_id("+8613800000000"));
+        assert!(!requires_sender_id("+447911123456"));
+        assert!(!requires_sender_id("not-a-number"));
+    }
+
+    #[test]
+    fn test_is_e164() {
+        assert!(is_e164("+8613800000000"));
+        assert!(is_e164("+12025550123"));
+        assert!(!is_e164("13800000000"));
+        assert!(!is_e164("008613800000000"));
+        assert!(!is_e164("+"));
+        assert!(!is_e164("+0123456789"));
+        assert!(!is_e164("+86 138 0000 0000"));
+        assert!(!is_e164("+1234567890123456"));
+    }
+}
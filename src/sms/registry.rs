@@ -0,0 +1,216 @@
+//! Per-template default signatures
+
+use crate::error::{Result, TencentCloudError};
+use crate::sms::models::SendSmsRequest;
+use crate::sms::params::NamedParams;
+use std::collections::HashMap;
+
+/// Signature and parameter count registered for a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateEntry {
+    /// Approved signature sent with the template
+    pub sign_name: String,
+    /// Number of parameters the template expects
+    pub param_count: usize,
+    /// Names of the parameters in template order, if registered by name
+    pub param_names: Vec<String>,
+}
+
+/// Mapping of template IDs to their signature and parameter count
+///
+/// Lets call sites send by template ID alone, with the signature looked up
+/// and the parameter count checked before anything is sent.
+///
+/// # Examples
+///
+/// ```rust
+/// use tencentcloud_sms_sdk::sms::TemplateRegistry;
+///
+/// let mut registry = TemplateRegistry::new("1400000000");
+/// registry.register("123456", "YourSignature", 2);
+///
+/// let request = registry
+///     .request(
+///         "123456",
+///         vec!["+8613800000000".to_string()],
+///         vec!["1234".to_string(), "5".to_string()],
+///     )
+///     .unwrap();
+/// assert_eq!(request.sign_name.as_deref(), Some("YourSignature"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateRegistry {
+    sms_sdk_app_id: String,
+    templates: HashMap<String, TemplateEntry>,
+}
+
+impl TemplateRegistry {
+    /// Create an empty registry for an SMS SDK App ID
+    pub fn new<S: Into<String>>(sms_sdk_app_id: S) -> Self {
+        Self {
+            sms_sdk_app_id: sms_sdk_app_id.into(),
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Register the signature and parameter count of a template
+    ///
+    /// Registering a template again replaces its previous entry.
+    pub fn register<T: Into<String>, S: Into<String>>(
+        &mut self,
+        template_id: T,
+        sign_name: S,
+        param_count: usize,
+    ) -> &mut Self {
+        self.templates.insert(
+            template_id.into(),
+            TemplateEntry {
+                sign_name: sign_name.into(),
+                param_count,
+                param_names: Vec::new(),
+            },
+        );
+        self
+    }
+
+    /// Register the signature and parameter names of a template
+    ///
+    /// The names give the parameter order for [`TemplateRegistry::named_params`];
+    /// the parameter count is the number of names.
+    pub fn register_named<T, S, I, N>(
+        &mut self,
+        template_id: T,
+        sign_name: S,
+        names: I,
+    ) -> &mut Self
+    where
+        T: Into<String>,
+        S: Into<String>,
+        I: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        let param_names: Vec<String> = names.into_iter().map(Into::into).collect();
+        self.templates.insert(
+            template_id.into(),
+            TemplateEntry {
+                sign_name: sign_name.into(),
+                param_count: param_names.len(),
+                param_names,
+            },
+        );
+        self
+    }
+
+    /// Create an empty [`NamedParams`] in the registered order of a template
+    ///
+    /// Returns `None` if the template is not registered or was registered
+    /// without parameter names.
+    pub fn named_params(&self, template_id: &str) -> Option<NamedParams> {
+        self.get(template_id)
+            .filter(|entry| !entry.param_names.is_empty())
+            .map(|entry| NamedParams::new(entry.param_names.iter().cloned()))
+    }
+
+    /// Get the entry of a template
+    pub fn get(&self, template_id: &str) -> Option<&TemplateEntry> {
+        self.templates.get(template_id)
+    }
+
+    /// Get the SMS SDK App ID requests are built for
+    pub fn get_sms_sdk_app_id(&self) -> &str {
+        &self.sms_sdk_app_id
+    }
+
+    /// Build a request for a registered template
+    ///
+    /// Fails with a parameter error if the template is not registered or
+    /// `params` does not have the registered number of parameters.
+    pub fn request(
+        &self,
+        template_id: &str,
+        phone_number_set: Vec<String>,
+        params: Vec<String>,
+    ) -> Result<SendSmsRequest> {
+        let entry = self.get(template_id).ok_or_else(|| {
+            TencentCloudError::parameter(format!("Template {} is not registered", template_id))
+        })?;
+        if params.len() != entry.param_count {
+            return Err(TencentCloudError::parameter(format!(
+                "Template {} expects {} parameters, got {}",
+                template_id,
+                entry.param_count,
+                params.len()
+            )));
+        }
+        Ok(SendSmsRequest::new(
+            phone_number_set,
+            self.sms_sdk_app_id.as_str(),
+            template_id,
+            entry.sign_name.as_str(),
+            params,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request() {
+        let mut registry = TemplateRegistry::new("1400000000");
+        registry
+            .register("123456", "TestSignature", 1)
+            .register("654321", "OtherSignature", 0);
+
+        let request = registry
+            .request(
+                "123456",
+                vec!["+8613800000000".to_string()],
+                vec!["1234".to_string()],
+            )
+            .unwrap();
+        assert_eq!(request.sms_sdk_app_id, "1400000000");
+        assert_eq!(request.template_id, "123456");
+        assert_eq!(request.sign_name.as_deref(), Some("TestSignature"));
+        assert_eq!(request.template_param_set, Some(vec!["1234".to_string()]));
+
+        let request = registry
+            .request("654321", vec!["+8613800000000".to_string()], vec![])
+            .unwrap();
+        assert_eq!(request.sign_name.as_deref(), Some("OtherSignature"));
+        assert!(request.template_param_set.is_none());
+
+        let error = registry
+            .request("123456", vec!["+8613800000000".to_string()], vec![])
+            .unwrap_err();
+        assert!(matches!(error, TencentCloudError::Parameter(_)));
+        assert!(registry
+            .request("999999", vec!["+8613800000000".to_string()], vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_named_params_from_registry() {
+        let mut registry = TemplateRegistry::new("1400000000");
+        registry
+            .register_named("123456", "TestSignature", ["code", "expire"])
+            .register("654321", "OtherSignature", 1);
+        assert_eq!(registry.get("123456").unwrap().param_count, 2);
+        assert!(registry.named_params("654321").is_none());
+
+        let mut params = registry.named_params("123456").unwrap();
+        params.set("expire", 5).set("code", "1234");
+        let request = registry
+            .request(
+                "123456",
+                vec!["+8613800000000".to_string()],
+                params.build().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            request.template_param_set,
+            Some(vec!["1234".to_string(), "5".to_string()])
+        );
+    }
+}
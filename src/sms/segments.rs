@@ -0,0 +1,142 @@
+//! Estimation of how many billed segments a message is split into
+
+/// Characters of the GSM 03.38 default alphabet
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+     ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/// Characters of the GSM 03.38 extension table, which take two septets
+const GSM7_EXTENDED: &str = "\u{c}^{}\\[~]|€";
+
+/// Character encoding a message is sent with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmsEncoding {
+    /// GSM 03.38 7-bit default alphabet
+    Gsm7,
+    /// UCS-2, used as soon as one character is outside the GSM alphabet
+    Ucs2,
+}
+
+impl SmsEncoding {
+    /// Get the maximum length of a single-segment message
+    pub fn single_segment_limit(&self) -> usize {
+        match self {
+            Self::Gsm7 => 160,
+            Self::Ucs2 => 70,
+        }
+    }
+
+    /// Get the length of each segment of a multi-segment message
+    ///
+    /// Smaller than the single-segment limit, since every segment carries a
+    /// concatenation header.
+    pub fn multi_segment_limit(&self) -> usize {
+        match self {
+            Self::Gsm7 => 153,
+            Self::Ucs2 => 67,
+        }
+    }
+}
+
+/// Estimated encoding and segment count of a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentEstimate {
+    /// Encoding the message is sent with
+    pub encoding: SmsEncoding,
+    /// Message length in septets for GSM-7 or UTF-16 code units for UCS-2
+    pub length: usize,
+    /// Number of segments, each billed separately
+    pub segments: usize,
+}
+
+impl SegmentEstimate {
+    /// Estimate the encoding and segment count of `text`
+    ///
+    /// Follows the GSM rules: 160 GSM-7 characters or 70 UCS-2 characters fit
+    /// in one segment, longer messages are split into segments of 153 or 67.
+    /// GSM-7 extension characters such as `€` count twice. Carriers may
+    /// differ in the details, so treat the result as an estimate. An empty
+    /// message counts as one segment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::sms::{SegmentEstimate, SmsEncoding};
+    ///
+    /// let estimate = SegmentEstimate::for_text("您的验证码是123456");
+    /// assert_eq!(estimate.encoding, SmsEncoding::Ucs2);
+    /// assert_eq!(estimate.segments, 1);
+    /// ```
+    pub fn for_text(text: &str) -> Self {
+        let gsm7_length = text.chars().try_fold(0, |length, c| {
+            if GSM7_BASIC.contains(c) {
+                Some(length + 1)
+            } else if GSM7_EXTENDED.contains(c) {
+                Some(length + 2)
+            } else {
+                None
+            }
+        });
+        let (encoding, length) = match gsm7_length {
+            Some(length) => (SmsEncoding::Gsm7, length),
+            None => (SmsEncoding::Ucs2, text.encode_utf16().count()),
+        };
+        let segments = if length <= encoding.single_segment_limit() {
+            1
+        } else {
+            length.div_ceil(encoding.multi_segment_limit())
+        };
+        Self {
+            encoding,
+            length,
+            segments,
+        }
+    }
+}
+
+/// Estimated cost of a send, from [`Client::estimate`](crate::Client::estimate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// Number of recipients
+    pub recipient_count: usize,
+    /// Segments billed per recipient
+    pub estimated_segments_per_recipient: usize,
+    /// Segments billed for the whole send
+    pub estimated_total_segments: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gsm7_segments() {
+        let estimate = SegmentEstimate::for_text("Your code is 123456");
+        assert_eq!(estimate.encoding, SmsEncoding::Gsm7);
+        assert_eq!(estimate.length, 19);
+        assert_eq!(estimate.segments, 1);
+
+        assert_eq!(SegmentEstimate::for_text(&"a".repeat(160)).segments, 1);
+        assert_eq!(SegmentEstimate::for_text(&"a".repeat(161)).segments, 2);
+        assert_eq!(SegmentEstimate::for_text(&"a".repeat(307)).segments, 3);
+
+        let estimate = SegmentEstimate::for_text("Pay 5€ [now]");
+        assert_eq!(estimate.encoding, SmsEncoding::Gsm7);
+        assert_eq!(estimate.length, 15);
+    }
+
+    #[test]
+    fn test_ucs2_segments() {
+        let estimate = SegmentEstimate::for_text("验证码");
+        assert_eq!(estimate.encoding, SmsEncoding::Ucs2);
+        assert_eq!(estimate.length, 3);
+        assert_eq!(estimate.segments, 1);
+
+        assert_eq!(SegmentEstimate::for_text(&"码".repeat(70)).segments, 1);
+        assert_eq!(SegmentEstimate::for_text(&"码".repeat(71)).segments, 2);
+        // One non-GSM character switches the whole message to UCS-2
+        let estimate = SegmentEstimate::for_text(&format!("{}😀", "a".repeat(69)));
+        assert_eq!(estimate.encoding, SmsEncoding::Ucs2);
+        assert_eq!(estimate.length, 71);
+        assert_eq!(estimate.segments, 2);
+    }
+}
@@ -0,0 +1,583 @@
+//! SMS signature models
+
+use crate::core::response::RequestId;
+use serde::{Deserialize, Serialize};
+
+/// Review status of an SMS signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignStatus {
+    /// Approved and ready for use
+    Approved,
+    /// Waiting for review
+    Pending,
+    /// Rejected by review
+    Rejected,
+}
+
+impl SignStatus {
+    /// Get the status from the `StatusCode` returned by the API
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            0 => Some(Self::Approved),
+            1 => Some(Self::Pending),
+            -1 => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Get the `StatusCode` value used by the API
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::Approved => 0,
+            Self::Pending => 1,
+            Self::Rejected => -1,
+        }
+    }
+}
+
+/// Common reasons for a rejected review, parsed from the review reply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// A trademark registration certificate is required but missing
+    TrademarkMissing,
+    /// The content does not match the supporting documents or company name
+    ContentMismatch,
+    /// The applicant is not authorized to use the name or brand
+    UnauthorizedUse,
+    /// Any other reason, with the original reply
+    Other(String),
+}
+
+impl RejectionReason {
+    /// Classify a free-text review reply
+    ///
+    /// This is best effort: replies are matched against keywords used in
+    /// common Chinese and English rejection notices, and anything unrecognized
+    /// is returned as [`RejectionReason::Other`].
+    pub fn classify(reply: &str) -> Self {
+        let lower = reply.to_lowercase();
+        let mentions = |keywords: &[&str]| keywords.iter().any(|keyword| lower.contains(keyword));
+
+        if mentions(&["商标", "trademark"]) {
+            Self::TrademarkMissing
+        } else if mentions(&["授权", "委托", "unauthori", "authorization"]) {
+            Self::UnauthorizedUse
+        } else if mentions(&[
+            "不一致",
+            "不符",
+            "mismatch",
+            "does not match",
+            "inconsistent",
+        ]) {
+            Self::ContentMismatch
+        } else {
+            Self::Other(reply.to_string())
+        }
+    }
+}
+
+/// Kind of entity an SMS signature names, the `SignType` of AddSmsSign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignType {
+    /// Company name
+    Company,
+    /// App name
+    App,
+    /// Website name
+    Website,
+    /// WeChat official account name
+    OfficialAccount,
+    /// Registered trademark
+    Trademark,
+    /// Government body, public institution or other organization
+    Government,
+    /// WeChat mini program name
+    MiniProgram,
+}
+
+impl SignType {
+    /// Get the `SignType` value used by the API
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            Self::Company => 0,
+            Self::App => 1,
+            Self::Website => 2,
+            Self::OfficialAccount => 3,
+            Self::Trademark => 4,
+            Self::Government => 5,
+            Self::MiniProgram => 6,
+        }
+    }
+}
+
+/// Supporting document submitted with a signature, the `DocumentType` of AddSmsSign
+///
+/// Each [`SignType`] accepts only some document types; see the AddSmsSign
+/// API documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentType {
+    /// Unified business license (three certificates in one)
+    ThreeInOne,
+    /// Business license
+    BusinessLicense,
+    /// Organization code certificate
+    OrganizationCode,
+    /// Unified social credit code certificate
+    SocialCreditCode,
+    /// Screenshot of the app's admin console, for apps by individuals
+    AppConsoleScreenshot,
+    /// Screenshot of the website's ICP filing, for websites by individuals
+    WebsiteFilingScreenshot,
+    /// Screenshot of the mini program settings, for mini programs by individuals
+    MiniProgramScreenshot,
+    /// Trademark registration certificate
+    TrademarkCertificate,
+    /// Screenshot of the official account settings, for accounts by individuals
+    OfficialAccountScreenshot,
+}
+
+impl DocumentType {
+    /// Get the `DocumentType` value used by the API
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            Self::ThreeInOne => 0,
+            Self::BusinessLicense => 1,
+            Self::OrganizationCode => 2,
+            Self::SocialCreditCode => 3,
+            Self::AppConsoleScreenshot => 4,
+            Self::WebsiteFilingScreenshot => 5,
+            Self::MiniProgramScreenshot => 6,
+            Self::TrademarkCertificate => 7,
+            Self::OfficialAccountScreenshot => 8,
+        }
+    }
+}
+
+/// Whose business a signature is for, the `SignPurpose` of AddSmsSign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignPurpose {
+    /// The account owner's own business
+    SelfUse,
+    /// Another party's business, which requires a power of attorney
+    Other,
+}
+
+impl SignPurpose {
+    /// Get the `SignPurpose` value used by the API
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            Self::SelfUse => 0,
+            Self::Other => 1,
+        }
+    }
+}
+
+/// Minimum length of a signature name, in characters excluding brackets
+pub const MIN_SIGN_NAME_CHARS: usize = 2;
+
+/// Maximum length of a signature name, in characters excluding brackets
+pub const MAX_SIGN_NAME_CHARS: usize = 12;
+
+/// Request structure for applying for a new SMS signature
+#[derive(Debug, Clone, Serialize)]
+pub struct AddSmsSignRequest {
+    /// Signature name
+    #[serde(rename = "SignName")]
+    pub sign_name: String,
+
+    /// Signature type, see [`SignType`]
+    #[serde(rename = "SignType")]
+    pub sign_type: u64,
+
+    /// Supporting document type, see [`DocumentType`]
+    #[serde(rename = "DocumentType")]
+    pub document_type: u64,
+
+    /// Whether the signature is for international SMS
+    /// 0: domestic, 1: international
+    #[serde(rename = "International")]
+    pub international: u64,
+
+    /// Signature purpose, see [`SignPurpose`]
+    #[serde(rename = "SignPurpose")]
+    pub sign_purpose: u64,
+
+    /// Supporting document image, Base64 encoded without the data URL prefix
+    #[serde(rename = "ProofImage")]
+    pub proof_image: String,
+
+    /// Power of attorney image, Base64 encoded
+    /// Required when the purpose is [`SignPurpose::Other`]
+    #[serde(rename = "CommissionImage", skip_serializing_if = "Option::is_none")]
+    pub commission_image: Option<String>,
+
+    /// Note for the reviewer
+    #[serde(rename = "Remark", skip_serializing_if = "Option::is_none")]
+    pub remark: Option<String>,
+}
+
+impl AddSmsSignRequest {
+    /// Create a new AddSmsSignRequest
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::sms::{AddSmsSignRequest, DocumentType, SignPurpose, SignType};
+    ///
+    /// let request = AddSmsSignRequest::new(
+    ///     "Acme",
+    ///     SignType::Company,
+    ///     DocumentType::ThreeInOne,
+    ///     false,
+    ///     SignPurpose::SelfUse,
+    ///     "base64-image",
+    /// );
+    /// assert_eq!(request.sign_type, 0);
+    /// ```
+    pub fn new<S: Into<String>>(
+        sign_name: S,
+        sign_type: SignType,
+        document_type: DocumentType,
+        international: bool,
+        sign_purpose: SignPurpose,
+        proof_image: S,
+    ) -> Self {
+        Self {
+            sign_name: sign_name.into(),
+            sign_type: sign_type.as_u64(),
+            document_type: document_type.as_u64(),
+            international: u64::from(international),
+            sign_purpose: sign_purpose.as_u64(),
+            proof_image: proof_image.into(),
+            commission_image: None,
+            remark: None,
+        }
+    }
+
+    /// Set the power of attorney image
+    pub fn set_commission_image<S: Into<String>>(&mut self, image: S) -> &mut Self {
+        self.commission_image = Some(image.into());
+        self
+    }
+
+    /// Set the note for the reviewer
+    pub fn set_remark<S: Into<String>>(&mut self, remark: S) -> &mut Self {
+        self.remark = Some(remark.into());
+        self
+    }
+
+    /// Validate the request parameters
+    ///
+    /// The signature name must be [`MIN_SIGN_NAME_CHARS`] to
+    /// [`MAX_SIGN_NAME_CHARS`] characters long, not counting the `【】` or
+    /// `[]` brackets around it. Characters are Unicode scalar values, so a
+    /// Chinese character counts as one.
+    pub fn validate(&self) -> Result<(), String> {
+        let length = self
+            .sign_name
+            .chars()
+            .filter(|c| !matches!(c, '【' | '】' | '[' | ']'))
+            .count();
+        if !(MIN_SIGN_NAME_CHARS..=MAX_SIGN_NAME_CHARS).contains(&length) {
+            return Err(format!(
+                "Sign name is {} characters, but must be {} to {} characters excluding brackets",
+                length, MIN_SIGN_NAME_CHARS, MAX_SIGN_NAME_CHARS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// IDs assigned to a new signature application
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AddSignStatus {
+    /// Signature ID
+    #[serde(rename = "SignId")]
+    pub sign_id: u64,
+
+    /// Application ID
+    #[serde(rename = "SignApplyId")]
+    pub sign_apply_id: u64,
+}
+
+/// Response structure for applying for a new SMS signature
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AddSmsSignResponse {
+    /// IDs assigned to the application
+    #[serde(rename = "AddSignStatus")]
+    pub add_sign_status: AddSignStatus,
+
+    /// Unique request ID
+    #[serde(rename = "RequestId")]
+    pub request_id: RequestId,
+}
+
+/// Request structure for querying SMS signature status
+#[derive(Debug, Clone, Serialize)]
+pub struct DescribeSmsSignListRequest {
+    /// IDs of the signatures to query
+    /// Maximum 100 IDs per request
+    #[serde(rename = "SignIdSet")]
+    pub sign_id_set: Vec<u64>,
+
+    /// Whether the signatures are for international SMS
+    /// 0: domestic, 1: international
+    #[serde(rename = "International")]
+    pub international: u64,
+}
+
+impl DescribeSmsSignListRequest {
+    /// Create a new DescribeSmsSignListRequest
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::sms::DescribeSmsSignListRequest;
+    ///
+    /// let request = DescribeSmsSignListRequest::new(vec![1234, 5678], false);
+    /// ```
+    pub fn new(sign_id_set: Vec<u64>, international: bool) -> Self {
+        Self {
+            sign_id_set,
+            international: u64::from(international),
+        }
+    }
+}
+
+/// Status information for a single SMS signature
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescribeSignListStatus {
+    /// Signature ID
+    #[serde(rename = "SignId")]
+    pub sign_id: u64,
+
+    /// Whether the signature is for international SMS
+    /// 0: domestic, 1: international
+    #[serde(rename = "International")]
+    pub international: u64,
+
+    /// Review status code
+    /// 0: approved, 1: pending, -1: rejected
+    #[serde(rename = "StatusCode")]
+    pub status_code: i64,
+
+    /// Review reply, explaining a rejection
+    #[serde(rename = "ReviewReply", default)]
+    pub review_reply: String,
+
+    /// Signature name
+    #[serde(rename = "SignName")]
+    pub sign_name: String,
+
+    /// Submission time as a UNIX timestamp in seconds
+    #[serde(rename = "CreateTime", default)]
+    pub create_time: u64,
+}
+
+impl DescribeSignListStatus {
+    /// Get the typed review status, if the status code is known
+    pub fn status(&self) -> Option<SignStatus> {
+        SignStatus::from_code(self.status_code)
+    }
+
+    /// Get the parsed rejection reason, if the signature was rejected
+    pub fn rejection_reason(&self) -> Option<RejectionReason> {
+        if self.status() != Some(SignStatus::Rejected) {
+            return None;
+        }
+        Some(RejectionReason::classify(&self.review_reply))
+    }
+}
+
+/// Response structure for querying SMS signature status
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescribeSmsSignListResponse {
+    /// Signature status list
+    #[serde(rename = "DescribeSignListStatusSet")]
+    pub describe_sign_list_status_set: Vec<DescribeSignListStatus>,
+
+    /// Unique request ID
+    #[serde(rename = "RequestId")]
+    pub request_id: RequestId,
+}
+
+impl DescribeSmsSignListResponse {
+    /// Get the signatures with the given review status
+    pub fn filter_by_status(&self, status: SignStatus) -> Vec<&DescribeSignListStatus> {
+        self.describe_sign_list_status_set
+            .iter()
+            .filter(|sign| sign.status() == Some(status))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_status_codes() {
+        for status in [
+            SignStatus::Approved,
+            SignStatus::Pending,
+            SignStatus::Rejected,
+        ] {
+            assert_eq!(SignStatus::from_code(status.code()), Some(status));
+        }
+        assert_eq!(SignStatus::from_code(2), None);
+    }
+
+    #[test]
+    fn test_sign_enum_values() {
+        let sign_types = [
+            SignType::Company,
+            SignType::App,
+            SignType::Website,
+            SignType::OfficialAccount,
+            SignType::Trademark,
+            SignType::Government,
+            SignType::MiniProgram,
+        ];
+        for (value, sign_type) in sign_types.iter().enumerate() {
+            assert_eq!(sign_type.as_u64(), value as u64);
+        }
+
+        let document_types = [
+            DocumentType::ThreeInOne,
+            DocumentType::BusinessLicense,
+            DocumentType::OrganizationCode,
+            DocumentType::SocialCreditCode,
+            DocumentType::AppConsoleScreenshot,
+            DocumentType::WebsiteFilingScreenshot,
+            DocumentType::MiniProgramScreenshot,
+            DocumentType::TrademarkCertificate,
+            DocumentType::OfficialAccountScreenshot,
+        ];
+        for (value, document_type) in document_types.iter().enumerate() {
+            assert_eq!(document_type.as_u64(), value as u64);
+        }
+
+        assert_eq!(SignPurpose::SelfUse.as_u64(), 0);
+        assert_eq!(SignPurpose::Other.as_u64(), 1);
+    }
+
+    #[test]
+    fn test_add_sms_sign_request_serialization() {
+        let mut request = AddSmsSignRequest::new(
+            "Acme",
+            SignType::Trademark,
+            DocumentType::TrademarkCertificate,
+            true,
+            SignPurpose::Other,
+            "cHJvb2Y=",
+        );
+        request.set_commission_image("Y29tbWlzc2lvbg==");
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["SignType"], 4);
+        assert_eq!(json["DocumentType"], 7);
+        assert_eq!(json["International"], 1);
+        assert_eq!(json["SignPurpose"], 1);
+        assert_eq!(json["CommissionImage"], "Y29tbWlzc2lvbg==");
+        assert!(json.get("Remark").is_none());
+    }
+
+    #[test]
+    fn test_add_sms_sign_request_validate() {
+        let request = |name: &str| {
+            AddSmsSignRequest::new(
+                name,
+                SignType::Company,
+                DocumentType::ThreeInOne,
+                false,
+                SignPurpose::SelfUse,
+                "base64-image",
+            )
+        };
+        assert!(request("腾讯云").validate().is_ok());
+        assert!(request("【腾讯云】").validate().is_ok());
+        assert!(request("腾讯云短信服务测试签名名").validate().is_ok());
+
+        let error = request("腾讯云短信服务测试签名名称")
+            .validate()
+            .unwrap_err();
+        assert!(error.contains("is 13 characters"), "{}", error);
+
+        let error = request("【腾】").validate().unwrap_err();
+        assert!(error.contains("is 1 characters"), "{}", error);
+    }
+
+    #[test]
+    fn test_filter_by_status() {
+        let response: DescribeSmsSignListResponse = serde_json::from_str(
+            r#"{
+                "DescribeSignListStatusSet": [
+                    {"SignId": 1, "International": 0, "StatusCode": 0, "ReviewReply": "", "SignName": "Approved", "CreateTime": 1600000000},
+                    {"SignId": 2, "International": 0, "StatusCode": -1, "ReviewReply": "Missing trademark", "SignName": "RejectedA", "CreateTime": 1600000000},
+                    {"SignId": 3, "International": 0, "StatusCode": 1, "ReviewReply": "", "SignName": "Pending", "CreateTime": 1600000000},
+                    {"SignId": 4, "International": 0, "StatusCode": -1, "ReviewReply": "Content mismatch", "SignName": "RejectedB", "CreateTime": 1600000000}
+                ],
+                "RequestId": "test-request-id"
+            }"#,
+        )
+        .unwrap();
+
+        let rejected = response.filter_by_status(SignStatus::Rejected);
+        let ids: Vec<u64> = rejected.iter().map(|sign| sign.sign_id).collect();
+        assert_eq!(ids, vec![2, 4]);
+        assert_eq!(response.filter_by_status(SignStatus::Pending).len(), 1);
+    }
+
+    #[test]
+    fn test_rejection_reason_classify() {
+        let cases = [
+            (
+                "签名为商标名称，请上传商标注册证书",
+                RejectionReason::TrademarkMissing,
+            ),
+            (
+                "Please provide the trademark registration certificate",
+                RejectionReason::TrademarkMissing,
+            ),
+            (
+                "签名与企业名称不一致，请修改",
+                RejectionReason::ContentMismatch,
+            ),
+            (
+                "Signature does not match the uploaded business license",
+                RejectionReason::ContentMismatch,
+            ),
+            (
+                "使用他人名称需提供授权委托书",
+                RejectionReason::UnauthorizedUse,
+            ),
+            (
+                "Unauthorized use of a third-party brand",
+                RejectionReason::UnauthorizedUse,
+            ),
+            (
+                "签名不能为纯数字",
+                RejectionReason::Other("签名不能为纯数字".to_string()),
+            ),
+        ];
+
+        for (reply, reason) in cases {
+            assert_eq!(RejectionReason::classify(reply), reason, "{}", reply);
+        }
+    }
+
+    #[test]
+    fn test_rejection_reason_only_for_rejected() {
+        let mut sign: DescribeSignListStatus = serde_json::from_str(
+            r#"{"SignId": 1, "International": 0, "StatusCode": -1, "ReviewReply": "Missing trademark", "SignName": "Test"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sign.rejection_reason(),
+            Some(RejectionReason::TrademarkMissing)
+        );
+
+        sign.status_code = SignStatus::Approved.code();
+        assert_eq!(sign.rejection_reason(), None);
+    }
+}
@@ -0,0 +1,196 @@
+//! SMS delivery status models
+
+use crate::core::response::RequestId;
+use crate::sms::SendSmsResponse;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of receipts returned by one PullSmsSendStatusByPhoneNumber call
+pub const MAX_PULL_STATUS_LIMIT: u64 = 100;
+
+/// Request structure for pulling delivery receipts of a phone number
+#[derive(Debug, Clone, Serialize)]
+pub struct PullSmsSendStatusByPhoneNumberRequest {
+    /// Start of the query range as a UNIX timestamp in seconds
+    #[serde(rename = "BeginTime")]
+    pub begin_time: u64,
+
+    /// Offset of the first receipt to return
+    #[serde(rename = "Offset")]
+    pub offset: u64,
+
+    /// Maximum number of receipts to return (at most 100)
+    #[serde(rename = "Limit")]
+    pub limit: u64,
+
+    /// Phone number in E.164 format
+    #[serde(rename = "PhoneNumber")]
+    pub phone_number: String,
+
+    /// SMS SDK App ID
+    #[serde(rename = "SmsSdkAppId")]
+    pub sms_sdk_app_id: String,
+
+    /// End of the query range as a UNIX timestamp in seconds
+    #[serde(rename = "EndTime", skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<u64>,
+}
+
+impl PullSmsSendStatusByPhoneNumberRequest {
+    /// Create a new request for the receipts of a phone number since `begin_time`
+    pub fn new<S: Into<String>>(phone_number: S, sms_sdk_app_id: S, begin_time: u64) -> Self {
+        Self {
+            begin_time,
+            offset: 0,
+            limit: MAX_PULL_STATUS_LIMIT,
+            phone_number: phone_number.into(),
+            sms_sdk_app_id: sms_sdk_app_id.into(),
+            end_time: None,
+        }
+    }
+}
+
+/// Delivery receipt of a single message
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PullSmsSendStatus {
+    /// Time the user received the message, as a UNIX timestamp in seconds
+    #[serde(rename = "UserReceiveTime", default)]
+    pub user_receive_time: Option<u64>,
+
+    /// Country/region code
+    #[serde(rename = "CountryCode", default)]
+    pub country_code: String,
+
+    /// Phone number without the country code
+    #[serde(rename = "SubscriberNumber", default)]
+    pub subscriber_number: String,
+
+    /// Phone number in E.164 format
+    #[serde(rename = "PhoneNumber")]
+    pub phone_number: String,
+
+    /// Serial number returned by the SMS sending API
+    #[serde(rename = "SerialNo")]
+    pub serial_no: String,
+
+    /// Delivery result, `SUCCESS` or `FAIL`
+    #[serde(rename = "ReportStatus")]
+    pub report_status: String,
+
+    /// Description of the delivery result
+    #[serde(rename = "Description", default)]
+    pub description: String,
+
+    /// User session context
+    #[serde(rename = "SessionContext", default)]
+    pub session_context: String,
+}
+
+impl PullSmsSendStatus {
+    /// Check if the message was delivered
+    pub fn is_delivered(&self) -> bool {
+        self.report_status == "SUCCESS"
+    }
+}
+
+/// Response structure for pulling delivery receipts of a phone number
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PullSmsSendStatusByPhoneNumberResponse {
+    /// Delivery receipts
+    #[serde(rename = "PullSmsSendStatusSet", default)]
+    pub pull_sms_send_status_set: Vec<PullSmsSendStatus>,
+
+    /// Unique request ID
+    #[serde(rename = "RequestId")]
+    pub request_id: RequestId,
+}
+
+/// Delivery state of one recipient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeliveryState {
+    /// The send itself failed, so there is nothing to deliver
+    NotSent,
+    /// No receipt arrived before the timeout
+    Pending,
+    /// The receipt reports successful delivery
+    Delivered,
+    /// The receipt reports a delivery failure
+    Failed,
+}
+
+/// Delivery outcome of one recipient
+#[derive(Debug, Clone)]
+pub struct DeliveryReport {
+    /// Phone number
+    pub phone_number: String,
+    /// Serial number returned by the SMS sending API
+    pub serial_no: String,
+    /// Delivery state
+    pub state: DeliveryState,
+    /// Receipt, once one has arrived
+    pub receipt: Option<PullSmsSendStatus>,
+}
+
+/// Result of [`Client::send_and_await_delivery`](crate::Client::send_and_await_delivery)
+#[derive(Debug, Clone)]
+pub struct DeliveryResult {
+    /// Response of the SendSms call
+    pub response: SendSmsResponse,
+    /// Delivery outcome of each recipient, in the order of the send statuses
+    pub reports: Vec<DeliveryReport>,
+}
+
+impl DeliveryResult {
+    /// Check if every recipient reported successful delivery
+    pub fn all_delivered(&self) -> bool {
+        self.reports
+            .iter()
+            .all(|report| report.state == DeliveryState::Delivered)
+    }
+
+    /// Get the reports still waiting for a receipt
+    pub fn pending(&self) -> Vec<&DeliveryReport> {
+        self.reports
+            .iter()
+            .filter(|report| report.state == DeliveryState::Pending)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_request_serialization() {
+        let request =
+            PullSmsSendStatusByPhoneNumberRequest::new("+8613800000000", "1400000000", 1620000000);
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"BeginTime":1620000000,"Offset":0,"Limit":100,"PhoneNumber":"+8613800000000","SmsSdkAppId":"1400000000"}"#
+        );
+    }
+
+    #[test]
+    fn test_pull_status_deserialization() {
+        let response: PullSmsSendStatusByPhoneNumberResponse = serde_json::from_str(
+            r#"{
+                "PullSmsSendStatusSet": [{
+                    "UserReceiveTime": 1620000010,
+                    "CountryCode": "86",
+                    "SubscriberNumber": "13800000000",
+                    "PhoneNumber": "+8613800000000",
+                    "SerialNo": "serial-0",
+                    "ReportStatus": "SUCCESS",
+                    "Description": "DELIVRD",
+                    "SessionContext": ""
+                }],
+                "RequestId": "test-request-id"
+            }"#,
+        )
+        .unwrap();
+
+        let receipt = &response.pull_sms_send_status_set[0];
+        assert!(receipt.is_delivered());
+        assert_eq!(receipt.user_receive_time, Some(1620000010));
+    }
+}
@@ -0,0 +1,118 @@
+//! SMS template models
+
+use crate::core::response::RequestId;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for querying SMS templates
+#[derive(Debug, Clone, Serialize)]
+pub struct DescribeSmsTemplateListRequest {
+    /// IDs of the templates to query
+    /// Maximum 100 IDs per request; omit to page through all templates
+    #[serde(rename = "TemplateIdSet", skip_serializing_if = "Option::is_none")]
+    pub template_id_set: Option<Vec<u64>>,
+
+    /// Whether the templates are for international SMS
+    /// 0: domestic, 1: international
+    #[serde(rename = "International")]
+    pub international: u64,
+
+    /// Maximum number of templates to return when paging
+    #[serde(rename = "Limit", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Offset of the first template to return when paging
+    #[serde(rename = "Offset", skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+}
+
+impl DescribeSmsTemplateListRequest {
+    /// Create a new request for the given template IDs
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tencentcloud_sms_sdk::sms::DescribeSmsTemplateListRequest;
+    ///
+    /// let request = DescribeSmsTemplateListRequest::new(vec![1234, 5678], false);
+    /// ```
+    pub fn new(template_id_set: Vec<u64>, international: bool) -> Self {
+        Self {
+            template_id_set: Some(template_id_set),
+            international: u64::from(international),
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Create a new request for one page of all templates
+    pub fn page(international: bool, limit: u64, offset: u64) -> Self {
+        Self {
+            template_id_set: None,
+            international: u64::from(international),
+            limit: Some(limit),
+            offset: Some(offset),
+        }
+    }
+}
+
+/// Status information for a single SMS template
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescribeTemplateListStatus {
+    /// Template ID
+    #[serde(rename = "TemplateId")]
+    pub template_id: u64,
+
+    /// Whether the template is for international SMS
+    /// 0: domestic, 1: international
+    #[serde(rename = "International")]
+    pub international: u64,
+
+    /// Review status code
+    /// 0: approved, 1: pending, -1: rejected
+    #[serde(rename = "StatusCode")]
+    pub status_code: i64,
+
+    /// Review reply, explaining a rejection
+    #[serde(rename = "ReviewReply", default)]
+    pub review_reply: String,
+
+    /// Template name
+    #[serde(rename = "TemplateName")]
+    pub template_name: String,
+
+    /// Submission time as a UNIX timestamp in seconds
+    #[serde(rename = "CreateTime", default)]
+    pub create_time: u64,
+}
+
+/// Response structure for querying SMS templates
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescribeSmsTemplateListResponse {
+    /// Template status list
+    #[serde(rename = "DescribeTemplateStatusSet")]
+    pub describe_template_status_set: Vec<DescribeTemplateListStatus>,
+
+    /// Unique request ID
+    #[serde(rename = "RequestId")]
+    pub request_id: RequestId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_serialization() {
+        let request = DescribeSmsTemplateListRequest::new(vec![1, 2], false);
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"TemplateIdSet":[1,2],"International":0}"#
+        );
+
+        let request = DescribeSmsTemplateListRequest::page(true, 1, 0);
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"International":1,"Limit":1,"Offset":0}"#
+        );
+    }
+}
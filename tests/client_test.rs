@@ -0,0 +1,1299 @@
+//! Client tests against a local mock of the TencentCloud API
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tencentcloud_sms_sdk::core::{
+    ConnectionPoolStats, ConstantBackoff, EndpointResolver, FixedClock, HttpProfile, QuotaBehavior,
+    RetryProfile, Scheme,
+};
+use tencentcloud_sms_sdk::sms::{
+    AddSmsSignRequest, BatchEvent, BatchOptions, CategoryPolicy, DescribeSmsTemplateListRequest,
+    DocumentType, SignPurpose, SignStatus, SignType, SmsCategory, SmsEncoding, TemplateRegistry,
+};
+use tencentcloud_sms_sdk::{Client, ClientProfile, Credential, SendSmsRequest, TencentCloudError};
+use tokio_util::sync::CancellationToken;
+
+fn sms_request() -> SendSmsRequest {
+    SendSmsRequest::new(
+        vec!["+8613800000000".to_string()],
+        "1400000000",
+        "123456",
+        "TestSignature",
+        vec!["123456".to_string()],
+    )
+}
+
+#[derive(Serialize)]
+struct FakeActionRequest {
+    #[serde(rename = "Limit")]
+    limit: u32,
+}
+
+#[derive(Deserialize)]
+struct FakeActionResponse {
+    #[serde(rename = "Total")]
+    total: u32,
+    #[serde(rename = "RequestId")]
+    request_id: String,
+}
+
+#[tokio::test]
+async fn test_call_custom_action() {
+    let server =
+        MockServer::with_json(r#"{"Response":{"Total":3,"RequestId":"custom-request-id"}}"#).await;
+    let client = server.client();
+
+    let response: FakeActionResponse = client
+        .call("DescribeFakeThings", &FakeActionRequest { limit: 5 })
+        .await
+        .unwrap();
+
+    assert_eq!(response.total, 3);
+    assert_eq!(response.request_id, "custom-request-id");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].header("Host"), Some(server.endpoint().as_str()));
+    assert_eq!(
+        requests[0].header("X-TC-Action"),
+        Some("DescribeFakeThings")
+    );
+    assert_eq!(requests[0].body, r#"{"Limit":5}"#);
+}
+
+#[tokio::test]
+async fn test_call_custom_action_api_error() {
+    let server = MockServer::with_json(&common::error_body(
+        "InvalidParameter",
+        "Limit is out of range",
+    ))
+    .await;
+    let client = server.client();
+
+    let result: tencentcloud_sms_sdk::Result<FakeActionResponse> = client
+        .call("DescribeFakeThings", &FakeActionRequest { limit: 5000 })
+        .await;
+
+    let error = result.err().unwrap();
+    assert!(error.is_api_error("InvalidParameter"));
+    assert_eq!(error.request_id(), Some("mock-request-id"));
+}
+
+#[tokio::test]
+async fn test_describe_signs_by_status() {
+    let server = MockServer::with_json(
+        r#"{"Response":{"DescribeSignListStatusSet":[
+            {"SignId":1,"International":0,"StatusCode":0,"ReviewReply":"","SignName":"Approved","CreateTime":1600000000},
+            {"SignId":2,"International":0,"StatusCode":-1,"ReviewReply":"Missing trademark","SignName":"Rejected","CreateTime":1600000000}
+        ],"RequestId":"mock-request-id"}}"#,
+    )
+    .await;
+    let client = server.client();
+
+    let rejected = client
+        .describe_signs_by_status(vec![1, 2], false, SignStatus::Rejected)
+        .await
+        .unwrap();
+
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].sign_id, 2);
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].header("X-TC-Action"),
+        Some("DescribeSmsSignList")
+    );
+    assert_eq!(requests[0].body, r#"{"SignIdSet":[1,2],"International":0}"#);
+}
+
+#[tokio::test]
+async fn test_send_sms_raw_rate_limit() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+            .with_header("X-RateLimit-Remaining", "7")
+            .with_header("X-RateLimit-Reset", "1700000060"),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let client = server.client();
+
+    let raw = client.send_sms_raw(sms_request()).await.unwrap();
+    assert!(raw.response.is_all_success());
+    assert_eq!(raw.rate_limit.remaining, Some(7));
+    assert_eq!(raw.rate_limit.reset_at, Some(1700000060));
+
+    let raw = client.send_sms_raw(sms_request()).await.unwrap();
+    assert!(raw.rate_limit.is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_response_envelope() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(r#"{"Code":"GatewayError","Message":"upstream unavailable"}"#),
+        MockResponse::json("<html><body>502 Bad Gateway</body></html>")
+            .with_header("Content-Type", "text/html"),
+    ])
+    .await;
+    let client = server.client();
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("missing Response envelope"));
+    assert!(message.contains("upstream unavailable"));
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    assert!(error.to_string().contains("502 Bad Gateway"));
+}
+
+#[tokio::test]
+async fn test_retry_transient_api_error() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::error_body("InternalError", "try again")),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(2)
+        .set_backoff(ConstantBackoff::new(Duration::from_millis(1)));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+
+    let response = client.send_sms(sms_request()).await.unwrap();
+    assert!(response.is_all_success());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_no_retry_for_client_error() {
+    let server = MockServer::with_json(&common::error_body(
+        "InvalidParameterValue.TemplateParameterFormatError",
+        "bad params",
+    ))
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(2)
+        .set_backoff(ConstantBackoff::new(Duration::from_millis(1)));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+
+    assert!(client.send_sms(sms_request()).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_cancelled_send_leaves_client_usable() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+            .with_delay(Duration::from_secs(5)),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let client = server.client();
+
+    // Dropped while waiting for the response
+    let cancelled =
+        tokio::time::timeout(Duration::from_millis(100), client.send_sms(sms_request())).await;
+    assert!(cancelled.is_err());
+
+    let response = client.send_sms(sms_request()).await.unwrap();
+    assert!(response.is_all_success());
+}
+
+#[tokio::test]
+async fn test_cancelled_send_releases_concurrency_permit() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+            .with_delay(Duration::from_secs(5)),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let mut profile = ClientProfile::new();
+    profile.set_max_concurrency(1);
+    let client = server.client_with_profile(profile);
+
+    // Dropped while holding the only permit
+    let cancelled =
+        tokio::time::timeout(Duration::from_millis(100), client.send_sms(sms_request())).await;
+    assert!(cancelled.is_err());
+
+    // A leaked permit would block this send forever
+    let response = tokio::time::timeout(Duration::from_secs(2), client.send_sms(sms_request()))
+        .await
+        .expect("permit of the cancelled send was not released")
+        .unwrap();
+    assert!(response.is_all_success());
+
+    // Nor is an in-flight entry left behind for shutdown to wait on
+    client.shutdown(Duration::ZERO).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cancelled_during_backoff_leaves_client_usable() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::error_body("InternalError", "try again")),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(1)
+        .set_backoff(ConstantBackoff::new(Duration::from_secs(60)));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+
+    // Dropped by select! while sleeping between attempts
+    tokio::select! {
+        _ = client.send_sms(sms_request()) => panic!("send should still be backing off"),
+        _ = tokio::time::sleep(Duration::from_millis(200)) => {}
+    }
+    assert_eq!(server.requests().len(), 1);
+
+    let response = client.send_sms(sms_request()).await.unwrap();
+    assert!(response.is_all_success());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_secret_key_masked_in_errors() {
+    // An endpoint echoing the credentials back into the error message
+    let server = MockServer::with_json(&common::error_body(
+        "AuthFailure.SignatureFailure",
+        "signature mismatch for secret key test_key",
+    ))
+    .await;
+    let client = server.client();
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    assert!(error.is_api_error("AuthFailure.SignatureFailure"));
+    assert!(!error.to_string().contains("test_key"));
+    assert!(!error.print_all().contains("test_key"));
+    assert!(error.to_string().contains("REDACTED"));
+}
+
+#[tokio::test]
+async fn test_send_sms_all_aborts_on_failure_rate() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+        MockResponse::json(&common::error_body("InternalError", "backend down")),
+    ])
+    .await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..5).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(1).set_abort_on_failure_rate(0.5);
+
+    let result = client.send_sms_all(request, &options).await;
+
+    // Rates after each chunk: 0/1, 1/2 (not above 0.5), 2/3 (abort)
+    assert!(result.aborted);
+    assert_eq!(result.chunks.len(), 3);
+    assert_eq!(result.failed_chunks(), 2);
+    assert_eq!(result.responses().count(), 1);
+    assert_eq!(result.unsent, vec!["+8613800000003", "+8613800000004"]);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_send_sms_all_without_threshold_sends_every_chunk() {
+    let server = MockServer::with_json(&common::error_body("InternalError", "backend down")).await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..3).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options
+        .set_chunk_size(2)
+        .set_template_content("Your code is {1}");
+
+    let result = client.send_sms_all(request, &options).await;
+    assert!(!result.aborted);
+    assert_eq!(result.chunks.len(), 2);
+    assert_eq!(result.chunks[1].phone_numbers, vec!["+8613800000002"]);
+    assert!(result.unsent.is_empty());
+    // The 【】 around the signature make the message UCS-2
+    assert_eq!(result.chunks[0].segments.encoding, SmsEncoding::Ucs2);
+    assert_eq!(result.segments_by_encoding()[&SmsEncoding::Ucs2], 3);
+}
+
+#[tokio::test]
+async fn test_send_sms_all_cancelled_after_first_chunk() {
+    let cancel = CancellationToken::new();
+    let server = MockServer::start({
+        let cancel = cancel.clone();
+        move |_, _| {
+            cancel.cancel();
+            MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+        }
+    })
+    .await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..6).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(2);
+
+    let result = client
+        .send_sms_all_cancellable(request, &options, &cancel)
+        .await;
+    assert!(result.cancelled);
+    assert!(result.aborted);
+    assert_eq!(result.chunks.len(), 1);
+    assert!(result.chunks[0].result.is_ok());
+    assert_eq!(result.unsent.len(), 4);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_send_sms_all_cancelled_during_quota_wait() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut profile = ClientProfile::new();
+    profile
+        .set_minute_quota(2)
+        .set_quota_behavior(QuotaBehavior::Wait);
+    let client = server.client_with_profile(profile);
+    let mut request = sms_request();
+    request.phone_number_set = (0..6).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(2);
+
+    // The second chunk waits for the minute window; cancel during that wait
+    let cancel = CancellationToken::new();
+    tokio::spawn({
+        let cancel = cancel.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            cancel.cancel();
+        }
+    });
+
+    let start = Instant::now();
+    let result = client
+        .send_sms_all_cancellable(request, &options, &cancel)
+        .await;
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(result.cancelled);
+    assert!(result.aborted);
+    assert!(result.stopped_by.is_none());
+    assert_eq!(result.chunks.len(), 1);
+    assert_eq!(result.unsent.len(), 4);
+    assert_eq!(result.unsent[0], "+8613800000002");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_session_token_header() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut client = server.client();
+    client.set_credential(Credential::new("test_id", "test_key", Some("sts-token")));
+
+    client.send_sms(sms_request()).await.unwrap();
+    client.send_sms(sms_request()).await.unwrap();
+
+    for request in server.requests() {
+        assert_eq!(request.header("x-tc-token"), Some("sts-token"));
+        let authorization = request.header("authorization").unwrap();
+        assert!(authorization.contains("SignedHeaders=content-type;host,"));
+        assert!(!authorization.contains("sts-token"));
+    }
+}
+
+#[tokio::test]
+async fn test_per_attempt_timeout_retries_slow_attempt() {
+    let body = common::send_sms_body(&[("+8613800000000", "Ok")]);
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&body).with_delay(Duration::from_secs(5)),
+        MockResponse::json(&body),
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(1)
+        .set_backoff(ConstantBackoff::new(Duration::from_millis(1)))
+        .set_per_attempt_timeout(Duration::from_millis(200));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+
+    let response = client.send_sms(sms_request()).await.unwrap();
+    assert!(response.is_all_success());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_sequential_sends_reuse_one_connection() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let client = server.client();
+
+    for _ in 0..5 {
+        client.send_sms(sms_request()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let clone = client.clone();
+    clone.send_sms(sms_request()).await.unwrap();
+
+    assert_eq!(server.requests().len(), 6);
+    assert_eq!(server.connection_count(), 1);
+}
+
+#[tokio::test]
+async fn test_connection_pool_stats() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    // Connections are counted by their DNS lookup, which needs a host name
+    let mut http_profile = HttpProfile::new();
+    http_profile
+        .set_endpoint(server.endpoint().replace("127.0.0.1", "localhost"))
+        .set_scheme(Scheme::Http)
+        .use_env_proxy(false);
+    let client = Client::with_profile(
+        Credential::new("test_id", "test_key", None),
+        "ap-guangzhou",
+        ClientProfile::with_http_profile(http_profile),
+    );
+    assert_eq!(
+        client.connection_pool_stats(),
+        Some(ConnectionPoolStats::default())
+    );
+
+    for _ in 0..3 {
+        client.send_sms(sms_request()).await.unwrap();
+    }
+    client.clone().send_sms(sms_request()).await.unwrap();
+
+    let stats = client.connection_pool_stats().unwrap();
+    assert_eq!(stats.requests, 4);
+    assert_eq!(stats.connections_opened, 1);
+    assert_eq!(stats.reused(), 3);
+    assert_eq!(server.connection_count(), 1);
+
+    // A caller-supplied HTTP client is not instrumented
+    let client = Client::with_http_client(
+        Credential::new("test_id", "test_key", None),
+        "ap-guangzhou",
+        ClientProfile::new(),
+        reqwest::Client::new(),
+    );
+    assert!(client.connection_pool_stats().is_none());
+}
+
+#[tokio::test]
+async fn test_connection_pool_stats_ip_endpoint() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut http_profile = HttpProfile::new();
+    http_profile.use_env_proxy(false);
+    let client = server.client_with_profile(ClientProfile::with_http_profile(http_profile));
+    assert!(server.endpoint().starts_with("127.0.0.1:"));
+    assert!(client.connection_pool_stats().is_some());
+
+    // The connector never resolves an IP address, so connections to it
+    // cannot be counted and no statistics are reported
+    client.send_sms(sms_request()).await.unwrap();
+    assert!(client.connection_pool_stats().is_none());
+    assert_eq!(server.connection_count(), 1);
+}
+
+#[tokio::test]
+async fn test_action_timeouts() {
+    let server = MockServer::start(|_, _| {
+        MockResponse::json(
+            r#"{"Response":{"DescribeTemplateStatusSet":[],"RequestId":"mock-request-id"}}"#,
+        )
+        .with_delay(Duration::from_millis(500))
+    })
+    .await;
+    let mut profile = ClientProfile::new();
+    profile
+        .set_action_timeout("SendSms", Duration::from_millis(100))
+        .set_action_timeout("DescribeSmsTemplateList", Duration::from_secs(5));
+    assert_eq!(
+        profile.get_action_timeout("SendSms"),
+        Duration::from_millis(100)
+    );
+    assert_eq!(
+        profile.get_action_timeout("DescribeSmsSignList"),
+        profile.http_profile.get_req_timeout()
+    );
+    let client = server.client_with_profile(profile);
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    assert!(
+        matches!(&error, TencentCloudError::Network(e) if e.is_timeout()),
+        "{}",
+        error
+    );
+
+    client
+        .describe_sms_template_list(DescribeSmsTemplateListRequest::page(false, 1, 0))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_total_timeout_bounds_all_attempts() {
+    let body = common::send_sms_body(&[("+8613800000000", "Ok")]);
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&body).with_delay(Duration::from_secs(5))
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(5)
+        .set_backoff(ConstantBackoff::new(Duration::from_millis(1)))
+        .set_per_attempt_timeout(Duration::from_millis(100))
+        .set_total_timeout(Duration::from_millis(250));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    assert!(error.is_timeout_error(), "{}", error);
+    assert!(error.to_string().contains("did not complete"), "{}", error);
+    assert!(server.requests().len() < 6);
+}
+
+#[tokio::test]
+async fn test_max_concurrency_bounds_in_flight_calls() {
+    let server = MockServer::with_responses(vec![MockResponse::json(&common::send_sms_body(&[(
+        "+8613800000000",
+        "Ok",
+    )]))
+    .with_delay(Duration::from_millis(50))])
+    .await;
+    let mut profile = ClientProfile::new();
+    profile.set_max_concurrency(2);
+    let client = Arc::new(server.client_with_profile(profile));
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.send_sms(sms_request()).await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 8);
+    assert!(server.max_in_flight() <= 2, "{}", server.max_in_flight());
+    assert!(server.max_in_flight() >= 1);
+}
+
+#[tokio::test]
+async fn test_send_sms_by_deadline() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let client = server.client();
+
+    let past = Instant::now() - Duration::from_millis(1);
+    let error = client
+        .send_sms_by_deadline(sms_request(), past)
+        .await
+        .unwrap_err();
+    assert!(error.is_timeout_error());
+    assert!(server.requests().is_empty());
+
+    let future = Instant::now() + Duration::from_secs(10);
+    let response = client
+        .send_sms_by_deadline(sms_request(), future)
+        .await
+        .unwrap();
+    assert!(response.is_all_success());
+}
+
+#[tokio::test]
+async fn test_response_missing_fields_reports_schema_mismatch() {
+    let server = MockServer::with_json(r#"{"Response":{"SendStatusSet":[]}}"#).await;
+    let client = server.client();
+
+    let error = client.send_sms(sms_request()).await.unwrap_err();
+    assert!(matches!(error, TencentCloudError::Other(_)), "{:?}", error);
+    let message = error.to_string();
+    assert!(message.contains("schema mismatch"), "{}", message);
+    assert!(message.contains("RequestId"), "{}", message);
+    assert!(message.contains(r#"{"SendStatusSet":[]}"#), "{}", message);
+}
+
+#[tokio::test]
+async fn test_explicit_nulls_body() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    server.client().send_sms(sms_request()).await.unwrap();
+    let mut profile = ClientProfile::new();
+    profile.set_explicit_nulls(true);
+    server
+        .client_with_profile(profile)
+        .send_sms(sms_request())
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert!(!requests[0].body.contains("ExtendCode"));
+    assert!(requests[1].body.contains(r#""ExtendCode":null"#));
+    assert!(requests[1].body.contains(r#""SenderId":null"#));
+    let skipped: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    let nulls: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+    assert_eq!(skipped["TemplateId"], nulls["TemplateId"]);
+}
+
+#[tokio::test]
+async fn test_legacy_error_shape_is_api_error() {
+    let server = MockServer::with_json(
+        r#"{"Error":{"Code":"AuthFailure.SecretIdNotFound","Message":"no such id"},"RequestId":"legacy-id"}"#,
+    )
+    .await;
+
+    let error = server.client().send_sms(sms_request()).await.unwrap_err();
+    assert_eq!(error.code(), Some("AuthFailure.SecretIdNotFound"));
+    assert_eq!(error.request_id(), Some("legacy-id"));
+}
+
+#[tokio::test]
+async fn test_gzip_error_body_is_decoded() {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(
+            common::error_body("FailedOperation.SignatureIncorrectOrUnapproved", "bad sign")
+                .as_bytes(),
+        )
+        .unwrap();
+    let server = MockServer::with_responses(vec![MockResponse::json("")
+        .with_status(400)
+        .with_header("Content-Encoding", "gzip")
+        .with_body_bytes(encoder.finish().unwrap())])
+    .await;
+
+    let error = server.client().send_sms(sms_request()).await.unwrap_err();
+    assert_eq!(
+        error.code(),
+        Some("FailedOperation.SignatureIncorrectOrUnapproved")
+    );
+    assert_eq!(error.request_id(), Some("mock-request-id"));
+}
+
+#[tokio::test]
+async fn test_invalid_utf8_body_is_reported() {
+    // "错误" encoded as GBK rather than UTF-8
+    let mut body = br#"{"Response":{"Error":{"Code":"FailedOperation","Message":""#.to_vec();
+    body.extend_from_slice(&[0xb4, 0xed, 0xce, 0xf3]);
+    body.extend_from_slice(br#""},"RequestId":"mock-request-id"}}"#);
+    let server =
+        MockServer::with_responses(vec![MockResponse::json("").with_body_bytes(body)]).await;
+
+    let error = server.client().send_sms(sms_request()).await.unwrap_err();
+    assert!(matches!(error, TencentCloudError::Other(_)));
+    assert!(
+        error
+            .to_string()
+            .contains("not valid UTF-8 (invalid byte at offset 58"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_utf8_error_status_body_is_reported() {
+    // "错误" encoded as GBK rather than UTF-8
+    let mut body = br#"{"Response":{"Error":{"Code":"FailedOperation","Message":""#.to_vec();
+    body.extend_from_slice(&[0xb4, 0xed, 0xce, 0xf3]);
+    body.extend_from_slice(br#""},"RequestId":"mock-request-id"}}"#);
+
+    for status in [400, 502] {
+        let server = MockServer::with_responses(vec![MockResponse::json("")
+            .with_body_bytes(body.clone())
+            .with_status(status)])
+        .await;
+
+        let error = server.client().send_sms(sms_request()).await.unwrap_err();
+        assert!(matches!(error, TencentCloudError::Other(_)), "{}", error);
+        assert!(
+            error
+                .to_string()
+                .contains("not valid UTF-8 (invalid byte at offset 58"),
+            "{}",
+            error
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_add_sms_sign() {
+    let server = MockServer::with_json(
+        r#"{"Response":{"AddSignStatus":{"SignId":1234,"SignApplyId":5678},"RequestId":"mock-request-id"}}"#,
+    )
+    .await;
+    let request = AddSmsSignRequest::new(
+        "Acme",
+        SignType::App,
+        DocumentType::BusinessLicense,
+        false,
+        SignPurpose::SelfUse,
+        "cHJvb2Y=",
+    );
+
+    let response = server.client().add_sms_sign(request).await.unwrap();
+    assert_eq!(response.add_sign_status.sign_id, 1234);
+    assert_eq!(response.add_sign_status.sign_apply_id, 5678);
+    let requests = server.requests();
+    assert_eq!(requests[0].header("x-tc-action"), Some("AddSmsSign"));
+    assert!(requests[0]
+        .body
+        .contains(r#""SignType":1,"DocumentType":1"#));
+}
+
+#[tokio::test]
+async fn test_send_sms_stream_yields_each_chunk() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..5).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(2);
+
+    let mut stream = Box::pin(client.send_sms_stream(request, &options));
+    let Some(BatchEvent::Chunk(first)) = stream.next().await else {
+        panic!("expected a chunk");
+    };
+    assert!(first.result.is_ok());
+    assert_eq!(
+        first.phone_numbers,
+        vec!["+8613800000000", "+8613800000001"]
+    );
+    assert_eq!(first.segments.segments, 1);
+    assert_eq!(server.requests().len(), 1);
+
+    let rest: Vec<_> = stream.collect().await;
+    assert_eq!(rest.len(), 2);
+    assert!(rest
+        .iter()
+        .all(|event| matches!(event, BatchEvent::Chunk(chunk) if chunk.result.is_ok())));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[2]
+        .body
+        .contains(r#""PhoneNumberSet":["+8613800000004"]"#));
+}
+
+#[tokio::test]
+async fn test_send_sms_stream_reports_abort_with_unsent_numbers() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+        MockResponse::json(&common::error_body("InternalError", "backend down")),
+    ])
+    .await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..5).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(1).set_abort_on_failure_rate(0.5);
+
+    let events: Vec<_> = client.send_sms_stream(request, &options).collect().await;
+
+    // Same chunks as send_sms_all, then the stop instead of a silent end
+    assert_eq!(events.len(), 4);
+    let BatchEvent::Chunk(failed) = &events[1] else {
+        panic!("expected a chunk");
+    };
+    assert!(failed.result.is_err());
+    assert_eq!(failed.phone_numbers, vec!["+8613800000001"]);
+    let BatchEvent::Stopped(stop) = &events[3] else {
+        panic!("expected the batch to stop");
+    };
+    assert_eq!(stop.unsent, vec!["+8613800000003", "+8613800000004"]);
+    assert!(!stop.cancelled);
+    assert!(stop.stopped_by.is_none());
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_send_sms_stream_cancelled_after_first_chunk() {
+    let cancel = CancellationToken::new();
+    let server = MockServer::start({
+        let cancel = cancel.clone();
+        move |_, _| {
+            cancel.cancel();
+            MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+        }
+    })
+    .await;
+    let client = server.client();
+    let mut request = sms_request();
+    request.phone_number_set = (0..6).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(2);
+
+    let events: Vec<_> = client
+        .send_sms_stream_cancellable(request, &options, &cancel)
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert!(matches!(&events[0], BatchEvent::Chunk(chunk) if chunk.result.is_ok()));
+    let BatchEvent::Stopped(stop) = &events[1] else {
+        panic!("expected the batch to stop");
+    };
+    assert!(stop.cancelled);
+    assert_eq!(stop.unsent.len(), 4);
+    assert_eq!(stop.unsent[0], "+8613800000002");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_send_with_retry_failed() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[
+            ("+8613800000000", "Ok"),
+            ("+8613800000001", "LimitExceeded.DeliveryFrequencyLimit"),
+            (
+                "+8613800000002",
+                "InvalidParameterValue.IncorrectPhoneNumber",
+            ),
+        ])),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000001", "Ok")])),
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry.set_backoff(ConstantBackoff::new(Duration::from_millis(1)));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let client = server.client_with_profile(profile);
+    let mut request = sms_request();
+    request.phone_number_set = vec![
+        "+8613800000000".to_string(),
+        "+8613800000001".to_string(),
+        "+8613800000002".to_string(),
+    ];
+
+    let response = client.send_with_retry_failed(request, 3).await.unwrap();
+
+    assert!(response.check_phone_success("+8613800000000"));
+    assert!(response.check_phone_success("+8613800000001"));
+    assert!(!response.check_phone_success("+8613800000002"));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1]
+        .body
+        .contains(r#""PhoneNumberSet":["+8613800000001"]"#));
+}
+
+#[derive(Debug)]
+struct RegionResolver {
+    guangzhou: String,
+    beijing: String,
+}
+
+impl EndpointResolver for RegionResolver {
+    fn resolve(&self, region: &str, _action: &str) -> String {
+        match region {
+            "ap-beijing" => self.beijing.clone(),
+            _ => self.guangzhou.clone(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_endpoint_resolver_routes_by_region() {
+    let body = common::send_sms_body(&[("+8613800000000", "Ok")]);
+    let guangzhou = MockServer::with_json(&body).await;
+    let beijing = MockServer::with_json(&body).await;
+    let mut profile = ClientProfile::new();
+    profile.set_endpoint_resolver(RegionResolver {
+        guangzhou: guangzhou.endpoint(),
+        beijing: beijing.endpoint(),
+    });
+    let mut client = guangzhou.client_with_profile(profile);
+
+    client.send_sms(sms_request()).await.unwrap();
+    client.set_region("ap-beijing");
+    client.send_sms(sms_request()).await.unwrap();
+
+    for (server, region) in [(&guangzhou, "ap-guangzhou"), (&beijing, "ap-beijing")] {
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("x-tc-region"), Some(region));
+        assert_eq!(requests[0].header("host"), Some(server.endpoint().as_str()));
+    }
+}
+
+#[tokio::test]
+async fn test_send_sms_raw_returns_signing_timestamp() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let time = chrono::DateTime::from_timestamp(1_609_459_200, 0).unwrap();
+    let mut profile = ClientProfile::new();
+    profile.set_clock(FixedClock::new(time));
+    let client = server.client_with_profile(profile);
+
+    let raw = client.send_sms_raw(sms_request()).await.unwrap();
+
+    assert_eq!(raw.timestamp, 1_609_459_200);
+    assert_eq!(
+        raw.request_id.as_ref().map(|id| id.as_str()),
+        Some("mock-request-id")
+    );
+    let requests = server.requests();
+    assert_eq!(requests[0].header("x-tc-timestamp"), Some("1609459200"));
+    assert!(requests[0]
+        .header("authorization")
+        .unwrap()
+        .contains("/2021-01-01/sms/tc3_request"));
+}
+
+#[tokio::test]
+async fn test_time_offset_shifts_signing_timestamp() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let time = chrono::DateTime::from_timestamp(1_609_459_200, 0).unwrap();
+    let mut profile = ClientProfile::new();
+    profile
+        .set_clock(FixedClock::new(time))
+        .set_time_offset(chrono::Duration::seconds(-90));
+    let client = server.client_with_profile(profile);
+
+    let raw = client.send_sms_raw(sms_request()).await.unwrap();
+
+    assert_eq!(raw.timestamp, 1_609_459_110);
+    let requests = server.requests();
+    assert_eq!(requests[0].header("x-tc-timestamp"), Some("1609459110"));
+    // The credential scope follows the adjusted time across midnight
+    assert!(requests[0]
+        .header("authorization")
+        .unwrap()
+        .contains("/2020-12-31/sms/tc3_request"));
+}
+
+#[tokio::test]
+async fn test_send_sms_all_stops_at_minute_quota() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut profile = ClientProfile::new();
+    profile
+        .set_minute_quota(3)
+        .set_quota_behavior(QuotaBehavior::Error);
+    let client = server.client_with_profile(profile);
+    let mut request = sms_request();
+    request.phone_number_set = (0..5).map(|i| format!("+861380000000{}", i)).collect();
+    let mut options = BatchOptions::new();
+    options.set_chunk_size(2);
+
+    let result = client.send_sms_all(request, &options).await;
+
+    // The first chunk fits the quota; the second would reach 4 of 3
+    assert!(result.aborted);
+    assert_eq!(result.chunks.len(), 1);
+    assert!(result.stopped_by.unwrap().is_quota_error());
+    assert_eq!(
+        result.unsent,
+        vec!["+8613800000002", "+8613800000003", "+8613800000004"]
+    );
+    assert_eq!(server.requests().len(), 1);
+}
+
+fn pull_status_body(receipts: &[(&str, &str, &str)]) -> String {
+    let receipts: Vec<serde_json::Value> = receipts
+        .iter()
+        .map(|(phone, serial_no, status)| {
+            serde_json::json!({
+                "UserReceiveTime": 1620000010,
+                "CountryCode": "86",
+                "SubscriberNumber": phone.trim_start_matches("+86"),
+                "PhoneNumber": phone,
+                "SerialNo": serial_no,
+                "ReportStatus": status,
+                "Description": "DELIVRD",
+                "SessionContext": ""
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "Response": {"PullSmsSendStatusSet": receipts, "RequestId": "mock-request-id"}
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_send_and_await_delivery() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+        MockResponse::json(&pull_status_body(&[])),
+        MockResponse::json(&pull_status_body(&[(
+            "+8613800000000",
+            "serial-0",
+            "SUCCESS",
+        )])),
+    ])
+    .await;
+    let client = server.client();
+
+    let result = client
+        .send_and_await_delivery(
+            sms_request(),
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+    assert!(result.all_delivered());
+    assert_eq!(result.reports[0].serial_no, "serial-0");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[1].header("x-tc-action"),
+        Some("PullSmsSendStatusByPhoneNumber")
+    );
+}
+
+#[tokio::test]
+async fn test_send_and_await_delivery_times_out() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+        MockResponse::json(&pull_status_body(&[])),
+    ])
+    .await;
+    let client = server.client();
+
+    let result = client
+        .send_and_await_delivery(
+            sms_request(),
+            Duration::from_millis(20),
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap();
+
+    assert!(!result.all_delivered());
+    assert_eq!(result.pending().len(), 1);
+}
+
+#[derive(Debug)]
+struct NoMarketing;
+
+impl CategoryPolicy for NoMarketing {
+    fn check(&self, category: SmsCategory, _request: &SendSmsRequest) -> Result<(), String> {
+        if category == SmsCategory::Marketing {
+            Err("marketing is paused".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_category_policy_vetoes_send() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut profile = ClientProfile::new();
+    profile.set_category_policy(NoMarketing);
+    let client = server.client_with_profile(profile);
+
+    let error = client
+        .send_sms(sms_request().with_category(SmsCategory::Marketing))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, TencentCloudError::Policy(_)));
+    assert!(server.requests().is_empty());
+
+    client
+        .send_sms(sms_request().with_category(SmsCategory::Verification))
+        .await
+        .unwrap();
+    client.send_sms(sms_request()).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_verify_credentials() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::error_body(
+            "AuthFailure.SecretIdNotFound",
+            "The SecretId is not found",
+        )),
+        MockResponse::json(
+            r#"{"Response":{"DescribeTemplateStatusSet":[],"RequestId":"mock-request-id"}}"#,
+        ),
+        MockResponse::json(&common::error_body(
+            "UnauthorizedOperation.SmsSdkAppIdVerifyFail",
+            "app not found",
+        )),
+    ])
+    .await;
+    let client = server.client();
+
+    let error = client.verify_credentials().await.unwrap_err();
+    assert!(matches!(error, TencentCloudError::Auth(_)));
+    assert!(error.to_string().contains("AuthFailure.SecretIdNotFound"));
+
+    client.verify_credentials().await.unwrap();
+    client.verify_credentials().await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].header("x-tc-action"),
+        Some("DescribeSmsTemplateList")
+    );
+    assert_eq!(
+        requests[0].body,
+        r#"{"International":0,"Limit":1,"Offset":0}"#
+    );
+}
+
+#[test]
+fn test_estimate_ucs2_send() {
+    let client = Client::new(Credential::new("test_id", "test_key", None), "ap-guangzhou");
+    let request = SendSmsRequest::new(
+        vec![
+            "+8613800000000".to_string(),
+            "+8613800000001".to_string(),
+            "+8613800000002".to_string(),
+        ],
+        "1400000000",
+        "123456",
+        "腾讯云",
+        vec!["123456".to_string(), "5".to_string()],
+    );
+
+    // 【腾讯云】 plus 66 rendered characters: 71 UCS-2 characters, two segments
+    let template = format!(
+        "您的验证码是{{1}}，请于{{2}}分钟内填写。{}",
+        "谢".repeat(44)
+    );
+    let estimate = client.estimate(&request, Some(&template)).unwrap();
+    assert_eq!(estimate.recipient_count, 3);
+    assert_eq!(estimate.estimated_segments_per_recipient, 2);
+    assert_eq!(estimate.estimated_total_segments, 6);
+
+    let estimate = client.estimate(&request, None).unwrap();
+    assert_eq!(estimate.estimated_total_segments, 3);
+
+    let mut invalid = request.clone();
+    invalid.phone_number_set.clear();
+    assert!(matches!(
+        client.estimate(&invalid, None),
+        Err(TencentCloudError::Parameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_send_for_template() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let mut registry = TemplateRegistry::new("1400000000");
+    registry.register("123456", "TestSignature", 1);
+    let mut profile = ClientProfile::new();
+    profile.set_template_registry(registry);
+    let client = server.client_with_profile(profile);
+
+    let response = client
+        .send_for_template(
+            "123456",
+            vec!["+8613800000000".to_string()],
+            vec!["1234".to_string()],
+        )
+        .await
+        .unwrap();
+    assert!(response.is_all_success());
+    let requests = server.requests();
+    assert!(requests[0].body.contains(r#""SignName":"TestSignature""#));
+    assert!(requests[0].body.contains(r#""TemplateId":"123456""#));
+
+    let error = client
+        .send_for_template(
+            "123456",
+            vec!["+8613800000000".to_string()],
+            vec!["1234".to_string(), "5".to_string()],
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, TencentCloudError::Parameter(_)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_warm_up() {
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::error_body(
+            "AuthFailure.SignatureFailure",
+            "The provided credentials could not be validated",
+        )),
+        MockResponse::json(
+            r#"{"Response":{"DescribeTemplateStatusSet":[],"RequestId":"mock-request-id"}}"#,
+        ),
+    ])
+    .await;
+    let client = server.client();
+
+    let error = client.warm_up().await.unwrap_err();
+    assert!(matches!(error, TencentCloudError::Auth(_)));
+    assert!(error.to_string().contains("AuthFailure.SignatureFailure"));
+
+    client.warm_up().await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+    // The signing key was derived by the first warm-up and reused since
+    let (hits, misses) = client.signing_cache_stats();
+    assert_eq!(misses, 1);
+    assert_eq!(hits, 3);
+
+    let mut client = server.client();
+    client.set_credential(Credential::new("", "", None));
+    let error = client.warm_up().await.unwrap_err();
+    assert!(matches!(error, TencentCloudError::Auth(_)));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_sends() {
+    let server = MockServer::start(|_, _| {
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")]))
+            .with_delay(Duration::from_millis(200))
+    })
+    .await;
+    let client = Arc::new(server.client());
+
+    let sends: Vec<_> = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.send_sms(sms_request()).await })
+        })
+        .collect();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    client.shutdown(Duration::from_secs(5)).await.unwrap();
+    for send in sends {
+        assert!(send.await.unwrap().unwrap().is_all_success());
+    }
+
+    // Idempotent, and new sends are refused
+    client.shutdown(Duration::from_secs(5)).await.unwrap();
+    assert!(client.send_sms(sms_request()).await.is_err());
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_shutdown_times_out() {
+    let server = MockServer::with_responses(vec![MockResponse::json(&common::send_sms_body(&[(
+        "+8613800000000",
+        "Ok",
+    )]))
+    .with_delay(Duration::from_secs(2))])
+    .await;
+    let client = Arc::new(server.client());
+
+    let send = {
+        let client = client.clone();
+        tokio::spawn(async move { client.send_sms(sms_request()).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let error = client
+        .shutdown(Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(error.is_timeout_error());
+    send.abort();
+}
@@ -0,0 +1,310 @@
+//! Shared test helpers: a minimal local HTTP server standing in for the TencentCloud API
+
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tencentcloud_sms_sdk::{Client, ClientProfile, Credential, HttpProfile, Scheme};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Request path
+    pub path: String,
+    /// Request headers with lowercased names
+    pub headers: Vec<(String, String)>,
+    /// Request body
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Get a header value by case-insensitive name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A canned response returned by the mock server
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Extra response headers
+    pub headers: Vec<(String, String)>,
+    /// Raw response body
+    pub body: Vec<u8>,
+    /// Delay before the response is written
+    pub delay: Option<Duration>,
+}
+
+impl MockResponse {
+    /// Create a 200 response with a JSON body
+    pub fn json(body: &str) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.as_bytes().to_vec(),
+            delay: None,
+        }
+    }
+
+    /// Set the HTTP status code
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Replace the body with raw bytes
+    pub fn with_body_bytes(mut self, body: Vec<u8>) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Delay the response
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+type Handler = dyn Fn(usize, &RecordedRequest) -> MockResponse + Send + Sync;
+
+/// A local HTTP/1.1 server recording requests and replying with canned responses
+pub struct MockServer {
+    port: u16,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    connections: Arc<AtomicUsize>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Start a server that computes each response from the request index and request
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(usize, &RecordedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = Self {
+            port,
+            requests: Arc::new(Mutex::new(Vec::new())),
+            connections: Arc::new(AtomicUsize::new(0)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::new(AtomicUsize::new(0)),
+        };
+
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = server.requests.clone();
+        let connections = server.connections.clone();
+        let in_flight = server.in_flight.clone();
+        let max_in_flight = server.max_in_flight.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(serve_connection(
+                    stream,
+                    handler.clone(),
+                    requests.clone(),
+                    in_flight.clone(),
+                    max_in_flight.clone(),
+                ));
+            }
+        });
+
+        server
+    }
+
+    /// Start a server replying with the given responses in order, repeating the last one
+    pub async fn with_responses(responses: Vec<MockResponse>) -> Self {
+        Self::start(move |index, _| responses[index.min(responses.len() - 1)].clone()).await
+    }
+
+    /// Start a server that always replies with the same JSON body
+    pub async fn with_json(body: &str) -> Self {
+        Self::with_responses(vec![MockResponse::json(body)]).await
+    }
+
+    /// Port the server listens on
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Endpoint host of the server, to be used with [`Scheme::Http`]
+    pub fn endpoint(&self) -> String {
+        format!("127.0.0.1:{}", self.port)
+    }
+
+    /// Create a client pointed at this server
+    pub fn client(&self) -> Client {
+        self.client_with_profile(ClientProfile::new())
+    }
+
+    /// Create a client pointed at this server, keeping the rest of the profile
+    pub fn client_with_profile(&self, mut profile: ClientProfile) -> Client {
+        let mut http_profile: HttpProfile = profile.get_http_profile().clone();
+        http_profile
+            .set_endpoint(self.endpoint())
+            .set_scheme(Scheme::Http);
+        profile.set_http_profile(http_profile);
+        let credential = Credential::new("test_id", "test_key", None);
+        Client::with_profile(credential, "ap-guangzhou", profile)
+    }
+
+    /// All requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of TCP connections accepted so far
+    pub fn connection_count(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// Highest number of requests handled concurrently
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+) {
+    let mut buffer = Vec::new();
+    loop {
+        let request = match read_request(&mut stream, &mut buffer).await {
+            Some(request) => request,
+            None => return,
+        };
+
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+
+        let response = {
+            let mut requests = requests.lock().unwrap();
+            let index = requests.len();
+            requests.push(request.clone());
+            handler(index, &request)
+        };
+
+        if let Some(delay) = response.delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let mut head = format!("HTTP/1.1 {} OK\r\n", response.status);
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n\r\n", response.body.len()));
+
+        let written = async {
+            stream.write_all(head.as_bytes()).await?;
+            stream.write_all(&response.body).await?;
+            stream.flush().await
+        }
+        .await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        if written.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_request(stream: &mut TcpStream, buffer: &mut Vec<u8>) -> Option<RecordedRequest> {
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos;
+        }
+        let mut chunk = [0u8; 4096];
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let body_start = header_end + 4;
+    while buffer.len() < body_start + content_length {
+        let mut chunk = [0u8; 4096];
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let body =
+        String::from_utf8_lossy(&buffer[body_start..body_start + content_length]).to_string();
+    buffer.drain(..body_start + content_length);
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Build an enveloped SendSms response body from `(phone, code)` pairs
+pub fn send_sms_body(statuses: &[(&str, &str)]) -> String {
+    let statuses: Vec<String> = statuses
+        .iter()
+        .enumerate()
+        .map(|(index, (phone, code))| {
+            format!(
+                r#"{{"SerialNo":"serial-{}","PhoneNumber":"{}","Fee":{},"SessionContext":"","Code":"{}","Message":"{}","IsoCode":"CN"}}"#,
+                index,
+                phone,
+                if *code == "Ok" { 1 } else { 0 },
+                code,
+                if *code == "Ok" { "send success" } else { "send failed" },
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"Response":{{"SendStatusSet":[{}],"RequestId":"mock-request-id"}}}}"#,
+        statuses.join(",")
+    )
+}
+
+/// Build an enveloped API error response body
+pub fn error_body(code: &str, message: &str) -> String {
+    format!(
+        r#"{{"Response":{{"Error":{{"Code":"{}","Message":"{}"}},"RequestId":"mock-request-id"}}}}"#,
+        code, message
+    )
+}
@@ -0,0 +1,36 @@
+//! Process-wide default client tests
+//!
+//! The default client can only be set once per process, so these tests live
+//! in their own test binary.
+
+mod common;
+
+use common::MockServer;
+use tencentcloud_sms_sdk::{default_client, init_default_client, send_sms, SendSmsRequest};
+
+fn sms_request() -> SendSmsRequest {
+    SendSmsRequest::new(
+        vec!["+8613800000000".to_string()],
+        "1400000000",
+        "123456",
+        "TestSignature",
+        vec!["123456".to_string()],
+    )
+}
+
+#[tokio::test]
+async fn test_send_through_default_client() {
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+
+    assert!(default_client().is_none());
+    let error = send_sms(sms_request()).await.unwrap_err();
+    assert!(error.to_string().contains("not initialized"), "{}", error);
+
+    init_default_client(server.client()).unwrap();
+    assert!(default_client().is_some());
+    assert!(init_default_client(server.client()).is_err());
+
+    let response = send_sms(sms_request()).await.unwrap();
+    assert!(response.is_all_success());
+    assert_eq!(server.requests().len(), 1);
+}
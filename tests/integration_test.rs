@@ -149,7 +149,7 @@ async fn test_send_status() {
         serial_no: "12345".to_string(),
         phone_number: "+8613800000000".to_string(),
         fee: 1,
-        session_context: "test".to_string(),
+        session_context: Some("test".to_string()),
         code: "Ok".to_string(),
         message: "Success".to_string(),
         iso_code: "CN".to_string(),
@@ -162,7 +162,7 @@ async fn test_send_status() {
         serial_no: "12345".to_string(),
         phone_number: "+8613800000000".to_string(),
         fee: 0,
-        session_context: "test".to_string(),
+        session_context: Some("test".to_string()),
         code: "InvalidParameterValue.IncorrectPhoneNumber".to_string(),
         message: "Invalid phone number".to_string(),
         iso_code: "CN".to_string(),
@@ -183,7 +183,7 @@ async fn test_send_sms_response() {
                 serial_no: "12345".to_string(),
                 phone_number: "+8613800000000".to_string(),
                 fee: 1,
-                session_context: "test".to_string(),
+                session_context: Some("test".to_string()),
                 code: "Ok".to_string(),
                 message: "Success".to_string(),
                 iso_code: "CN".to_string(),
@@ -192,13 +192,13 @@ async fn test_send_sms_response() {
                 serial_no: "12346".to_string(),
                 phone_number: "+8613800000001".to_string(),
                 fee: 0,
-                session_context: "test".to_string(),
+                session_context: Some("test".to_string()),
                 code: "InvalidParameterValue.IncorrectPhoneNumber".to_string(),
                 message: "Invalid phone number".to_string(),
                 iso_code: "CN".to_string(),
             },
         ],
-        request_id: "test-request-id".to_string(),
+        request_id: "test-request-id".into(),
     };
 
     assert!(!response.is_all_success());
@@ -274,7 +274,7 @@ async fn test_cpp_like_usage_pattern() {
     let client_profile = ClientProfile::with_http_profile(http_profile);
 
     // Create client
-    let client = Client::with_profile(credential, "ap-guangzhou", client_profile);
+    let _client = Client::with_profile(credential, "ap-guangzhou", client_profile);
 
     // Create request
     let request = SendSmsRequest::new(
@@ -310,5 +310,4 @@ fn test_library_exports() {
     let _error = TencentCloudError::api("TestError", "Test message");
 
     // If this compiles, it means all exports are working correctly
-    assert!(true);
 }
@@ -0,0 +1,76 @@
+//! Proxy selection tests
+//!
+//! These tests set proxy environment variables, so they live in their own
+//! test binary to keep other tests from seeing them.
+
+mod common;
+
+use common::MockServer;
+use tencentcloud_sms_sdk::{
+    Client, ClientProfile, Credential, HttpProfile, Scheme, SendSmsRequest,
+};
+
+/// A proxy address nothing listens on
+const DEAD_PROXY: &str = "http://127.0.0.1:9";
+
+fn sms_request() -> SendSmsRequest {
+    SendSmsRequest::new(
+        vec!["+8613800000000".to_string()],
+        "1400000000",
+        "123456",
+        "TestSignature",
+        vec!["123456".to_string()],
+    )
+}
+
+fn client(http_profile: HttpProfile) -> Client {
+    let mut profile = ClientProfile::new();
+    profile.set_http_profile(http_profile);
+    Client::with_profile(
+        Credential::new("test_id", "test_key", None),
+        "ap-guangzhou",
+        profile,
+    )
+}
+
+#[tokio::test]
+async fn test_explicit_proxy_overrides_env() {
+    std::env::set_var("HTTP_PROXY", DEAD_PROXY);
+    let proxy = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+
+    // The endpoint is unreachable, so only the explicit proxy can answer
+    let mut http_profile = HttpProfile::new();
+    http_profile
+        .set_endpoint("sms.example.invalid")
+        .set_scheme(Scheme::Http)
+        .set_proxy_host(Some("127.0.0.1"))
+        .set_proxy_port(Some(proxy.port()));
+
+    client(http_profile).send_sms(sms_request()).await.unwrap();
+
+    let requests = proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("host"), Some("sms.example.invalid"));
+}
+
+#[tokio::test]
+async fn test_env_proxy_disabled() {
+    std::env::set_var("HTTP_PROXY", DEAD_PROXY);
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+
+    let mut http_profile = HttpProfile::new();
+    http_profile
+        .set_endpoint(server.endpoint())
+        .set_scheme(Scheme::Http)
+        .use_env_proxy(false);
+    client(http_profile.clone())
+        .send_sms(sms_request())
+        .await
+        .unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    // With the environment honored, the request goes to the dead proxy
+    http_profile.use_env_proxy(true);
+    assert!(client(http_profile).send_sms(sms_request()).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
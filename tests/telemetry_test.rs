@@ -0,0 +1,97 @@
+//! OpenTelemetry span tests, run with `--features opentelemetry`
+#![cfg(feature = "opentelemetry")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use std::time::Duration;
+use tencentcloud_sms_sdk::{ClientProfile, ConstantBackoff, RetryProfile, SendSmsRequest};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[tokio::test]
+async fn test_send_sms_emits_span() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let guard = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")))
+        .set_default();
+
+    let server = MockServer::with_json(&common::send_sms_body(&[("+8613800000000", "Ok")])).await;
+    let request = SendSmsRequest::new(
+        vec!["+8613800000000".to_string()],
+        "1400000000",
+        "123456",
+        "TestSignature",
+        vec!["123456".to_string()],
+    );
+    server.client().send_sms(request).await.unwrap();
+
+    drop(guard);
+    provider.force_flush().unwrap();
+    let spans = exporter.get_finished_spans().unwrap();
+    let span = spans
+        .iter()
+        .find(|span| span.name == "sms/SendSms")
+        .expect("no span for the SendSms call");
+    let attribute = |key: &str| {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.as_str().into_owned())
+    };
+    assert_eq!(attribute("rpc.system").as_deref(), Some("tencentcloud"));
+    assert_eq!(attribute("rpc.service").as_deref(), Some("sms"));
+    assert_eq!(attribute("rpc.method").as_deref(), Some("SendSms"));
+    assert_eq!(attribute("peer.service"), Some(server.endpoint()));
+    assert_eq!(attribute("request_id").as_deref(), Some("mock-request-id"));
+}
+
+#[tokio::test]
+async fn test_retried_send_emits_one_span() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let guard = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")))
+        .set_default();
+
+    let server = MockServer::with_responses(vec![
+        MockResponse::json(&common::error_body("InternalError", "try again")),
+        MockResponse::json(&common::send_sms_body(&[("+8613800000000", "Ok")])),
+    ])
+    .await;
+    let mut retry = RetryProfile::new();
+    retry
+        .set_max_retries(1)
+        .set_backoff(ConstantBackoff::new(Duration::from_millis(1)));
+    let mut profile = ClientProfile::new();
+    profile.set_retry_profile(retry);
+    let request = SendSmsRequest::new(
+        vec!["+8613800000000".to_string()],
+        "1400000000",
+        "123456",
+        "TestSignature",
+        vec!["123456".to_string()],
+    );
+    server
+        .client_with_profile(profile)
+        .send_sms(request)
+        .await
+        .unwrap();
+
+    drop(guard);
+    provider.force_flush().unwrap();
+    assert_eq!(server.requests().len(), 2);
+    let spans = exporter.get_finished_spans().unwrap();
+    let count = spans
+        .iter()
+        .filter(|span| span.name == "sms/SendSms")
+        .count();
+    assert_eq!(count, 1, "each call must be instrumented exactly once");
+}